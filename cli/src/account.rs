@@ -0,0 +1,72 @@
+//! Decoding of record accounts from `solana account --output json` dumps
+
+use {
+    anyhow::{anyhow, bail, Context, Result},
+    base64::{engine::general_purpose::STANDARD, Engine},
+    serde_json::{json, Value},
+    sns_records::state::{record::Record, record_header::VersionedRecordHeader},
+    solana_sdk::{program_pack::Pack, pubkey::Pubkey},
+    spl_name_service::state::NameRecordHeader,
+    std::str::FromStr,
+};
+
+/// Extracts the address, owner program and data of an account dump
+fn parse_dump(dump: &Value) -> Result<(Option<Pubkey>, Pubkey, Vec<u8>)> {
+    let pubkey = dump
+        .get("pubkey")
+        .and_then(Value::as_str)
+        .map(Pubkey::from_str)
+        .transpose()
+        .context("Invalid account public key")?;
+    let account = dump.get("account").unwrap_or(dump);
+    let owner = account
+        .get("owner")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("Missing account owner"))?;
+    let owner = Pubkey::from_str(owner).context("Invalid account owner")?;
+    let data = match account.get("data") {
+        Some(Value::Array(data)) => match data.as_slice() {
+            [Value::String(data), Value::String(encoding)] if encoding == "base64" => {
+                STANDARD.decode(data).context("Invalid account data")?
+            }
+            _ => bail!("Account data must be base64 encoded"),
+        },
+        _ => bail!("Missing account data"),
+    };
+    Ok((pubkey, owner, data))
+}
+
+/// Returns the content as a string when it is valid UTF-8
+fn content_json(content: &[u8]) -> Value {
+    std::str::from_utf8(content).map_or(Value::Null, |content| json!(content))
+}
+
+pub fn decode_record_account(dump: &Value) -> Result<Value> {
+    let (pubkey, owner, data) = parse_dump(dump)?;
+    if owner != spl_name_service::ID {
+        bail!("The account is not owned by the name service program");
+    }
+    let name_header =
+        NameRecordHeader::unpack_from_slice(&data).map_err(|_| anyhow!("Invalid name header"))?;
+    let record = Record::deserialize(&data).map_err(|e| anyhow!("Invalid record: {}", e))?;
+    let version = match record.versioned_header() {
+        VersionedRecordHeader::V1(_) => 1,
+        VersionedRecordHeader::V2(header) => header.version,
+    };
+    let header = record.header();
+
+    Ok(json!({
+        "pubkey": pubkey.map(|key| key.to_string()),
+        "domain": name_header.parent_name.to_string(),
+        "class": name_header.class.to_string(),
+        "headerVersion": version,
+        "pendingUpload": record.versioned_header().is_pending_upload(),
+        "stalenessValidation": format!("{:?}", record.staleness_validation()),
+        "rightOfAssociationValidation": format!("{:?}", record.right_of_association_validation()),
+        "stalenessId": hex::encode(record.staleness_id()),
+        "roaId": hex::encode(record.roa_id()),
+        "contentLength": header.content_length,
+        "content": content_json(record.content()),
+        "contentHex": hex::encode(record.content()),
+    }))
+}
@@ -0,0 +1,720 @@
+//! Builds the instructions of the program from the command line arguments
+
+use {
+    crate::{
+        keys::{get_record_key, parse_domain, parse_record},
+        utils::{parse_fixed_hex, parse_hex, parse_pubkey},
+    },
+    anyhow::{anyhow, bail, Context, Result},
+    clap::{Arg, ArgGroup, ArgMatches, Command},
+    sns_records::{
+        instruction::{
+            allocate_and_post_record, allocate_record,
+            batch_records::{self, Operation},
+            begin_upload, check_address_record, delete_record, edit_record,
+            edit_record_preserve_staleness, finalize_upload, migrate_record,
+            patch_record::{self, Patch},
+            unverify_roa, validate_bitcoin_signature, validate_ed25519_signature,
+            validate_ethereum_signature::{self, EthereumMessageFormat},
+            validate_nostr_signature, validate_passkey_signature, validate_signature,
+            validate_solana_signature, write_chunk, write_roa,
+            xchain_record_operation::{self, XChainOperation},
+        },
+        state::{
+            validation::{BitcoinAddressType, Validation},
+            xchain_nonce::XChainNonce,
+        },
+    },
+    solana_sdk::{
+        hash::hash, instruction::Instruction, pubkey::Pubkey, system_program, sysvar::instructions,
+    },
+    std::fs,
+};
+
+/// The accounts shared by the instructions
+struct Keys {
+    fee_payer: Pubkey,
+    domain: Pubkey,
+    domain_owner: Pubkey,
+    record: Option<(String, Pubkey)>,
+}
+
+impl Keys {
+    fn parse(matches: &ArgMatches) -> Result<Self> {
+        let fee_payer = parse_pubkey(matches.value_of("fee-payer").unwrap())?;
+        let domain = parse_domain(matches.value_of("domain").unwrap())?;
+        let domain_owner = matches
+            .value_of("domain-owner")
+            .map(parse_pubkey)
+            .transpose()?
+            .unwrap_or(fee_payer);
+        // Batches derive the keys of their records from the operations
+        let record = matches
+            .try_get_one::<String>("record")
+            .ok()
+            .flatten()
+            .map(|record| parse_record(record))
+            .transpose()?
+            .map(|record| {
+                let key = get_record_key(&domain, &record);
+                (record, key)
+            });
+        Ok(Self {
+            fee_payer,
+            domain,
+            domain_owner,
+            record,
+        })
+    }
+
+    fn record_name(&self) -> String {
+        self.record.as_ref().unwrap().0.clone()
+    }
+
+    fn record_key(&self) -> &Pubkey {
+        &self.record.as_ref().unwrap().1
+    }
+}
+
+fn arg(name: &'static str, help: &'static str) -> Arg<'static> {
+    Arg::new(name).long(name).takes_value(true).help(help)
+}
+
+fn record_arg() -> Arg<'static> {
+    arg("record", "The record name, e.g. TXT").required(true)
+}
+
+fn content_args() -> [Arg<'static>; 3] {
+    [
+        arg("content", "The content as a UTF-8 string"),
+        arg("content-hex", "The content as hexadecimal bytes"),
+        arg("content-file", "The path of a file holding the content"),
+    ]
+}
+
+fn no_content_validation_arg() -> Arg<'static> {
+    Arg::new("no-content-validation")
+        .long("no-content-validation")
+        .help("Skip the on-chain check of the content against the type of the record")
+}
+
+fn content_group() -> ArgGroup<'static> {
+    ArgGroup::new("content-source")
+        .args(&["content", "content-hex", "content-file"])
+        .required(true)
+}
+
+fn parse_content(matches: &ArgMatches) -> Result<Vec<u8>> {
+    if let Some(content) = matches.value_of("content") {
+        Ok(content.as_bytes().to_vec())
+    } else if let Some(content) = matches.value_of("content-hex") {
+        parse_hex(content)
+    } else {
+        let path = matches.value_of("content-file").unwrap();
+        fs::read(path).with_context(|| format!("Failed to read {}", path))
+    }
+}
+
+fn parse_u32(matches: &ArgMatches, name: &str) -> Result<u32> {
+    let value = matches.value_of(name).unwrap();
+    value
+        .parse()
+        .with_context(|| format!("Invalid {}: {}", name, value))
+}
+
+fn parse_validation(validation: &str) -> Result<Validation> {
+    Ok(match validation {
+        "none" => Validation::None,
+        "solana" => Validation::Solana,
+        "ethereum" => Validation::Ethereum,
+        "unverified-solana" => Validation::UnverifiedSolana,
+        "xchain" => Validation::XChain,
+        "bitcoin" => Validation::Bitcoin,
+        "nostr" => Validation::Nostr,
+        "passkey" => Validation::Passkey,
+        "unverified-ethereum" => Validation::UnverifiedEthereum,
+        "unverified-bitcoin" => Validation::UnverifiedBitcoin,
+        "unverified-nostr" => Validation::UnverifiedNostr,
+        "unverified-passkey" => Validation::UnverifiedPasskey,
+        _ => bail!("Unknown validation {}", validation),
+    })
+}
+
+const VALIDATIONS: [&str; 12] = [
+    "none",
+    "solana",
+    "ethereum",
+    "unverified-solana",
+    "xchain",
+    "bitcoin",
+    "nostr",
+    "passkey",
+    "unverified-ethereum",
+    "unverified-bitcoin",
+    "unverified-nostr",
+    "unverified-passkey",
+];
+
+fn validation_arg(help: &'static str) -> Arg<'static> {
+    arg("validation", help)
+        .possible_values(VALIDATIONS)
+        .required(true)
+}
+
+/// Parses a batch operation, `post:<record>:<content>`, `edit:<record>:<content>` or
+/// `delete:<record>`
+fn parse_operation(operation: &str, validate_content: bool) -> Result<Operation> {
+    let mut parts = operation.splitn(3, ':');
+    let operation = match (parts.next(), parts.next(), parts.next()) {
+        (Some("post"), Some(record), Some(content)) => Operation::AllocateAndPost {
+            record: parse_record(record)?,
+            content: content.as_bytes().to_vec(),
+            validate_content,
+        },
+        (Some("edit"), Some(record), Some(content)) => Operation::Edit {
+            record: parse_record(record)?,
+            content: content.as_bytes().to_vec(),
+            validate_content,
+        },
+        (Some("delete"), Some(record), None) => Operation::Delete {
+            record: parse_record(record)?,
+        },
+        _ => bail!("Invalid operation {}", operation),
+    };
+    Ok(operation)
+}
+
+/// Arguments shared by all the instructions
+fn common_args() -> [Arg<'static>; 5] {
+    [
+        arg("fee-payer", "The public key of the fee payer").required(true),
+        arg("domain", "The domain, as a public key or a .sol name").required(true),
+        arg(
+            "domain-owner",
+            "The public key of the domain owner, defaults to the fee payer",
+        ),
+        arg("blockhash", "The recent blockhash of the transaction").required(true),
+        arg(
+            "keypair",
+            "The path of a keypair signing the transaction, can be repeated",
+        )
+        .multiple_occurrences(true),
+    ]
+}
+
+fn instruction_command(name: &'static str, about: &'static str) -> Command<'static> {
+    Command::new(name).about(about).args(common_args())
+}
+
+/// The subcommands of `build`, one per instruction of the program
+pub fn instruction_commands() -> Vec<Command<'static>> {
+    vec![
+        instruction_command("allocate-record", "Allocate an empty record").args([
+            record_arg(),
+            arg("content-length", "The length of the record content").required(true),
+        ]),
+        instruction_command("allocate-and-post-record", "Allocate and post a record")
+            .args([record_arg(), no_content_validation_arg()])
+            .args(content_args())
+            .group(content_group()),
+        instruction_command("edit-record", "Edit the content of a record")
+            .args([record_arg(), no_content_validation_arg()])
+            .args(content_args())
+            .group(content_group()),
+        instruction_command(
+            "validate-solana-signature",
+            "Validate the staleness or the RoA of a record with a Solana signer",
+        )
+        .args([
+            record_arg(),
+            arg(
+                "verifier",
+                "The signing verifier, defaults to the domain owner",
+            ),
+            Arg::new("staleness")
+                .long("staleness")
+                .help("Validate the staleness instead of the RoA"),
+        ]),
+        instruction_command(
+            "validate-ethereum-signature",
+            "Validate a record with an Ethereum signature",
+        )
+        .args([
+            record_arg(),
+            validation_arg("The validation to write"),
+            arg("signature", "The hexadecimal signature").required(true),
+            arg("expected-pubkey", "The hexadecimal Ethereum address").required(true),
+            arg("format", "The format of the signed message")
+                .possible_values(["eip191", "eip712"])
+                .default_value("eip191"),
+        ]),
+        instruction_command("delete-record", "Delete a record").arg(record_arg()),
+        instruction_command("write-roa", "Write the RoA ID of a record").args([
+            record_arg(),
+            arg("roa-id", "The hexadecimal RoA ID").required(true),
+            arg("validation", "The unverified kind of the RoA ID")
+                .possible_values(
+                    VALIDATIONS
+                        .iter()
+                        .copied()
+                        .filter(|v| v.starts_with("unverified-")),
+                )
+                .default_value("unverified-solana"),
+        ]),
+        instruction_command("unverify-roa", "Remove the RoA validation of a record").args([
+            record_arg(),
+            arg(
+                "verifier",
+                "The domain owner or the RoA ID signing the instruction, defaults to the domain \
+                 owner",
+            ),
+        ]),
+        instruction_command("begin-upload", "Begin a chunked upload").args([
+            record_arg(),
+            arg("content-length", "The total length of the uploaded content").required(true),
+        ]),
+        instruction_command("write-chunk", "Write a chunk of a chunked upload")
+            .args([
+                record_arg(),
+                arg("offset", "The offset of the chunk in the content").required(true),
+            ])
+            .args(content_args())
+            .group(content_group()),
+        instruction_command(
+            "finalize-upload",
+            "Finalize a chunked upload of the given content",
+        )
+        .arg(record_arg())
+        .args(content_args())
+        .group(content_group()),
+        instruction_command("patch-record", "Overwrite a range of the record content")
+            .args([
+                record_arg(),
+                arg("offset", "The offset of the patch in the content").required(true),
+            ])
+            .args(content_args())
+            .group(content_group()),
+        instruction_command(
+            "edit-record-preserve-staleness",
+            "Edit the content of a record while keeping its staleness validation",
+        )
+        .arg(record_arg())
+        .args(content_args())
+        .group(content_group()),
+        instruction_command("batch-records", "Allocate, edit and delete several records").args([
+            arg(
+                "operation",
+                "An operation: post:<record>:<content>, edit:<record>:<content> or \
+                 delete:<record>",
+            )
+            .multiple_occurrences(true)
+            .required(true),
+            no_content_validation_arg(),
+        ]),
+        instruction_command(
+            "validate-ed25519-signature",
+            "Validate the RoA of a record with an Ed25519 signature, preceded by the Ed25519 \
+             program instruction",
+        )
+        .args([
+            record_arg(),
+            arg("verifier", "The Ed25519 public key of the verifier").required(true),
+            arg("signature", "The hexadecimal signature").required(true),
+            arg("message", "The hexadecimal signed message").required(true),
+        ]),
+        instruction_command(
+            "validate-bitcoin-signature",
+            "Validate the RoA of a record with a Bitcoin signature",
+        )
+        .args([
+            record_arg(),
+            arg("signature", "The hexadecimal BIP-137 signature").required(true),
+            arg("address-type", "The type of the Bitcoin address")
+                .possible_values(["p2pkh", "p2wpkh"])
+                .required(true),
+            arg(
+                "pubkey-hash",
+                "The hexadecimal HASH160 of the Bitcoin public key",
+            )
+            .required(true),
+        ]),
+        instruction_command(
+            "validate-nostr-signature",
+            "Validate the RoA of a record with a Nostr signature",
+        )
+        .args([
+            record_arg(),
+            arg("signature", "The hexadecimal BIP-340 signature").required(true),
+            arg("expected-pubkey", "The hexadecimal x-only public key").required(true),
+        ]),
+        instruction_command(
+            "validate-passkey-signature",
+            "Validate the RoA of a record with a passkey, preceded by the secp256r1 program \
+             instruction",
+        )
+        .args([
+            record_arg(),
+            arg("pubkey", "The hexadecimal compressed P-256 public key").required(true),
+            arg("signature", "The hexadecimal signature").required(true),
+            arg("authenticator-data", "The hexadecimal authenticator data").required(true),
+            arg("client-data-json", "The client data JSON").required(true),
+        ]),
+        instruction_command(
+            "validate-signature",
+            "Validate the RoA of a record with the verifier of the validation",
+        )
+        .args([
+            record_arg(),
+            validation_arg("The validation of the RoA"),
+            arg("signature", "The hexadecimal signature").required(true),
+            arg("roa-id", "The hexadecimal RoA ID").required(true),
+        ]),
+        instruction_command("migrate-record", "Migrate the header of a record").arg(record_arg()),
+        instruction_command(
+            "xchain-record-operation",
+            "Edit or delete a record of a XChain domain with the authorization of its foreign \
+             owner, the domain owner is the XDomain account",
+        )
+        .args([
+            record_arg(),
+            arg("nonce", "The nonce of the domain").required(true),
+            arg("signature", "The hexadecimal EIP-191 signature").required(true),
+            Arg::new("delete")
+                .long("delete")
+                .help("Delete the record instead of editing it"),
+            no_content_validation_arg(),
+        ])
+        .args(content_args())
+        .group(content_group().arg("delete")),
+        instruction_command(
+            "check-address-record",
+            "Check that the RoA of a SOL, ETH or BTC record is verified for its address",
+        )
+        .arg(record_arg()),
+    ]
+}
+
+/// Builds the instructions of a `build` subcommand
+pub fn build_instructions(name: &str, matches: &ArgMatches) -> Result<Vec<Instruction>> {
+    let keys = Keys::parse(matches)?;
+    let central_state = sns_records::central_state::KEY;
+
+    macro_rules! accounts {
+        ($module:ident { $($field:ident: $value:expr),* $(,)? }) => {
+            $module::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &keys.fee_payer,
+                domain: &keys.domain,
+                central_state: &central_state,
+                $($field: $value,)*
+            }
+        };
+    }
+
+    let instructions = match name {
+        "allocate-record" => vec![sns_records::instruction::allocate_record(
+            accounts!(allocate_record {
+                record: keys.record_key(),
+                domain_owner: &keys.domain_owner,
+            }),
+            allocate_record::Params {
+                content_length: parse_u32(matches, "content-length")?,
+                record: keys.record_name(),
+            },
+        )],
+        "allocate-and-post-record" => vec![sns_records::instruction::allocate_and_post_record_v2(
+            accounts!(allocate_and_post_record {
+                record: keys.record_key(),
+                domain_owner: &keys.domain_owner,
+            }),
+            allocate_and_post_record::ParamsV2 {
+                record: keys.record_name(),
+                content: parse_content(matches)?,
+                validate_content: !matches.is_present("no-content-validation"),
+            },
+        )],
+        "edit-record" => vec![sns_records::instruction::edit_record_v2(
+            accounts!(edit_record {
+                record: keys.record_key(),
+                domain_owner: &keys.domain_owner,
+            }),
+            edit_record::ParamsV2 {
+                record: keys.record_name(),
+                content: parse_content(matches)?,
+                validate_content: !matches.is_present("no-content-validation"),
+            },
+        )],
+        "validate-solana-signature" => {
+            let verifier = matches
+                .value_of("verifier")
+                .map(parse_pubkey)
+                .transpose()?
+                .unwrap_or(keys.domain_owner);
+            vec![sns_records::instruction::validate_solana_signature(
+                accounts!(validate_solana_signature {
+                    record: keys.record_key(),
+                    domain_owner: &keys.domain_owner,
+                    verifier: &verifier,
+                }),
+                validate_solana_signature::Params {
+                    staleness: matches.is_present("staleness"),
+                },
+            )]
+        }
+        "validate-ethereum-signature" => {
+            vec![sns_records::instruction::validate_ethereum_signature_v2(
+                accounts!(validate_ethereum_signature {
+                    record: keys.record_key(),
+                    domain_owner: &keys.domain_owner,
+                }),
+                validate_ethereum_signature::ParamsV2 {
+                    validation: parse_validation(matches.value_of("validation").unwrap())?,
+                    signature: parse_hex(matches.value_of("signature").unwrap())?,
+                    expected_pubkey: parse_hex(matches.value_of("expected-pubkey").unwrap())?,
+                    format: match matches.value_of("format").unwrap() {
+                        "eip712" => EthereumMessageFormat::Eip712,
+                        _ => EthereumMessageFormat::Eip191,
+                    },
+                },
+            )]
+        }
+        "delete-record" => vec![sns_records::instruction::delete_record(
+            accounts!(delete_record {
+                record: keys.record_key(),
+                domain_owner: &keys.domain_owner,
+            }),
+            delete_record::Params {},
+        )],
+        "write-roa" => vec![sns_records::instruction::write_roa_v2(
+            accounts!(write_roa {
+                record: keys.record_key(),
+                domain_owner: &keys.domain_owner,
+            }),
+            write_roa::ParamsV2 {
+                validation: parse_validation(matches.value_of("validation").unwrap())?,
+                roa_id: parse_hex(matches.value_of("roa-id").unwrap())?,
+            },
+        )],
+        "unverify-roa" => {
+            let verifier = matches
+                .value_of("verifier")
+                .map(parse_pubkey)
+                .transpose()?
+                .unwrap_or(keys.domain_owner);
+            vec![sns_records::instruction::unverify_roa(
+                accounts!(unverify_roa {
+                    record: keys.record_key(),
+                    verifier: &verifier,
+                }),
+                unverify_roa::Params {},
+            )]
+        }
+        "begin-upload" => vec![sns_records::instruction::begin_upload(
+            accounts!(begin_upload {
+                record: keys.record_key(),
+                domain_owner: &keys.domain_owner,
+            }),
+            begin_upload::Params {
+                record: keys.record_name(),
+                content_length: parse_u32(matches, "content-length")?,
+            },
+        )],
+        "write-chunk" => vec![sns_records::instruction::write_chunk(
+            accounts!(write_chunk {
+                record: keys.record_key(),
+                domain_owner: &keys.domain_owner,
+            }),
+            write_chunk::Params {
+                offset: parse_u32(matches, "offset")?,
+                data: parse_content(matches)?,
+            },
+        )],
+        "finalize-upload" => {
+            let content = parse_content(matches)?;
+            vec![sns_records::instruction::finalize_upload(
+                accounts!(finalize_upload {
+                    record: keys.record_key(),
+                    domain_owner: &keys.domain_owner,
+                }),
+                finalize_upload::Params {
+                    content_length: content.len() as u32,
+                    content_hash: hash(&content).to_bytes(),
+                },
+            )]
+        }
+        "patch-record" => vec![sns_records::instruction::patch_record(
+            accounts!(patch_record {
+                record: keys.record_key(),
+                domain_owner: &keys.domain_owner,
+            }),
+            patch_record::Params {
+                patches: vec![Patch {
+                    offset: parse_u32(matches, "offset")?,
+                    data: parse_content(matches)?,
+                }],
+            },
+        )],
+        "edit-record-preserve-staleness" => {
+            vec![sns_records::instruction::edit_record_preserve_staleness(
+                accounts!(edit_record_preserve_staleness {
+                    record: keys.record_key(),
+                    domain_owner: &keys.domain_owner,
+                }),
+                edit_record_preserve_staleness::Params {
+                    record: keys.record_name(),
+                    content: parse_content(matches)?,
+                },
+            )]
+        }
+        "batch-records" => {
+            let validate_content = !matches.is_present("no-content-validation");
+            let operations = matches
+                .values_of("operation")
+                .unwrap()
+                .map(|operation| parse_operation(operation, validate_content))
+                .collect::<Result<Vec<_>>>()?;
+            let records = operations
+                .iter()
+                .map(|operation| get_record_key(&keys.domain, operation.record()))
+                .collect::<Vec<_>>();
+            vec![sns_records::instruction::batch_records(
+                accounts!(batch_records {
+                    domain_owner: &keys.domain_owner,
+                    records: &records,
+                }),
+                batch_records::Params { operations },
+            )]
+        }
+        "validate-ed25519-signature" => {
+            let verifier = parse_pubkey(matches.value_of("verifier").unwrap())?;
+            let signature = parse_fixed_hex(matches.value_of("signature").unwrap())?;
+            let message = parse_hex(matches.value_of("message").unwrap())?;
+            vec![
+                validate_ed25519_signature::new_ed25519_instruction(
+                    &verifier, &signature, &message,
+                ),
+                sns_records::instruction::validate_ed25519_signature(
+                    accounts!(validate_ed25519_signature {
+                        record: keys.record_key(),
+                        instructions_sysvar: &instructions::ID,
+                    }),
+                    validate_ed25519_signature::Params {},
+                ),
+            ]
+        }
+        "validate-bitcoin-signature" => {
+            vec![sns_records::instruction::validate_bitcoin_signature(
+                accounts!(validate_bitcoin_signature {
+                    record: keys.record_key(),
+                    domain_owner: &keys.domain_owner,
+                }),
+                validate_bitcoin_signature::Params {
+                    signature: parse_hex(matches.value_of("signature").unwrap())?,
+                    address_type: match matches.value_of("address-type").unwrap() {
+                        "p2wpkh" => BitcoinAddressType::P2wpkh,
+                        _ => BitcoinAddressType::P2pkh,
+                    },
+                    expected_pubkey_hash: parse_fixed_hex(
+                        matches.value_of("pubkey-hash").unwrap(),
+                    )?,
+                },
+            )]
+        }
+        "validate-nostr-signature" => vec![sns_records::instruction::validate_nostr_signature(
+            accounts!(validate_nostr_signature {
+                record: keys.record_key(),
+                domain_owner: &keys.domain_owner,
+            }),
+            validate_nostr_signature::Params {
+                signature: parse_fixed_hex(matches.value_of("signature").unwrap())?,
+                expected_pubkey: parse_fixed_hex(matches.value_of("expected-pubkey").unwrap())?,
+            },
+        )],
+        "validate-passkey-signature" => {
+            let authenticator_data = parse_hex(matches.value_of("authenticator-data").unwrap())?;
+            let client_data_json = matches
+                .value_of("client-data-json")
+                .unwrap()
+                .as_bytes()
+                .to_vec();
+            vec![
+                validate_passkey_signature::new_secp256r1_instruction(
+                    &parse_fixed_hex(matches.value_of("pubkey").unwrap())?,
+                    &parse_fixed_hex(matches.value_of("signature").unwrap())?,
+                    &authenticator_data,
+                    &client_data_json,
+                ),
+                sns_records::instruction::validate_passkey_signature(
+                    accounts!(validate_passkey_signature {
+                        record: keys.record_key(),
+                        domain_owner: &keys.domain_owner,
+                        instructions_sysvar: &instructions::ID,
+                    }),
+                    validate_passkey_signature::Params {
+                        authenticator_data,
+                        client_data_json,
+                    },
+                ),
+            ]
+        }
+        "validate-signature" => vec![sns_records::instruction::validate_signature(
+            accounts!(validate_signature {
+                record: keys.record_key(),
+                domain_owner: &keys.domain_owner,
+            }),
+            validate_signature::Params {
+                validation: parse_validation(matches.value_of("validation").unwrap())?,
+                signature: parse_hex(matches.value_of("signature").unwrap())?,
+                roa_id: parse_hex(matches.value_of("roa-id").unwrap())?,
+            },
+        )],
+        "migrate-record" => vec![sns_records::instruction::migrate_record(
+            accounts!(migrate_record {
+                record: keys.record_key(),
+                domain_owner: &keys.domain_owner,
+            }),
+            migrate_record::Params {},
+        )],
+        "xchain-record-operation" => {
+            let nonce = matches.value_of("nonce").unwrap();
+            let operation = if matches.is_present("delete") {
+                XChainOperation::Delete {
+                    record: keys.record_name(),
+                }
+            } else {
+                XChainOperation::Edit {
+                    record: keys.record_name(),
+                    content: parse_content(matches)?,
+                }
+            };
+            vec![sns_records::instruction::xchain_record_operation(
+                accounts!(xchain_record_operation {
+                    record: keys.record_key(),
+                    domain_owner: &keys.domain_owner,
+                    nonce: &XChainNonce::find_key(&keys.domain).0,
+                }),
+                xchain_record_operation::Params {
+                    operation,
+                    nonce: nonce
+                        .parse()
+                        .with_context(|| format!("Invalid nonce: {}", nonce))?,
+                    signature: parse_hex(matches.value_of("signature").unwrap())?,
+                    validate_content: !matches.is_present("no-content-validation"),
+                },
+            )]
+        }
+        "check-address-record" => vec![sns_records::instruction::check_address_record(
+            check_address_record::Accounts {
+                record: keys.record_key(),
+                domain: &keys.domain,
+            },
+            check_address_record::Params {
+                record: keys.record_name(),
+            },
+        )],
+        _ => return Err(anyhow!("Unknown instruction {}", name)),
+    };
+
+    Ok(instructions)
+}
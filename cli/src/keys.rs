@@ -0,0 +1,64 @@
+//! Derivation of domain and record keys
+
+use {
+    anyhow::{bail, Result},
+    sns_records::{
+        record::RecordName,
+        utils::{get_hashed_name, get_record_key_and_seeds, ROOT_DOMAIN},
+    },
+    solana_sdk::pubkey::Pubkey,
+    spl_name_service::state::get_seeds_and_key,
+    std::str::FromStr,
+};
+
+/// Prefix of the hashed names of subdomains
+const SUBDOMAIN_PREFIX: &str = "\0";
+
+/// Parses a domain given either as a public key or as a `.sol` name, e.g. `bonfida.sol` or
+/// `dex.bonfida.sol`
+pub fn parse_domain(domain: &str) -> Result<Pubkey> {
+    if let Ok(key) = Pubkey::from_str(domain) {
+        return Ok(key);
+    }
+    let labels = domain
+        .strip_suffix(".sol")
+        .unwrap_or(domain)
+        .split('.')
+        .collect::<Vec<_>>();
+    match labels.as_slice() {
+        [name] if !name.is_empty() => Ok(get_domain_key(name, None)),
+        [sub, name] if !sub.is_empty() && !name.is_empty() => {
+            let parent = get_domain_key(name, None);
+            Ok(get_domain_key(
+                &format!("{}{}", SUBDOMAIN_PREFIX, sub),
+                Some(&parent),
+            ))
+        }
+        _ => bail!("Invalid domain {}", domain),
+    }
+}
+
+fn get_domain_key(name: &str, parent: Option<&Pubkey>) -> Pubkey {
+    let (key, _) = get_seeds_and_key(
+        &spl_name_service::ID,
+        get_hashed_name(name),
+        None,
+        Some(parent.unwrap_or(&ROOT_DOMAIN)),
+    );
+    key
+}
+
+pub fn get_record_key(domain: &Pubkey, record: &str) -> Pubkey {
+    let (key, _) = get_record_key_and_seeds(domain, record);
+    key
+}
+
+/// Parses a record name, rejecting the misspellings of the SNS-IP-3 records, e.g. `SoL`
+///
+/// Custom record names are accepted as is
+pub fn parse_record(record: &str) -> Result<String> {
+    if let Some(expected) = RecordName::find_misspelled(record) {
+        bail!("Unknown record {}, did you mean {}?", record, expected);
+    }
+    Ok(record.to_owned())
+}
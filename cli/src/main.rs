@@ -0,0 +1,98 @@
+//! Offline tooling for the SNS Records program
+//!
+//! None of the commands require an RPC connection: transactions are built against a blockhash
+//! given on the command line and accounts are decoded from `solana account --output json` dumps.
+
+use {
+    anyhow::{Context, Result},
+    clap::{Arg, Command},
+    std::fs,
+};
+
+mod account;
+mod build;
+mod keys;
+mod transaction;
+mod utils;
+
+fn main() -> Result<()> {
+    let matches = Command::new("sns-records")
+        .about("Build and inspect SNS Records transactions offline")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("record-key")
+                .about("Derive the key of a record")
+                .arg(
+                    Arg::new("domain")
+                        .long("domain")
+                        .takes_value(true)
+                        .required(true)
+                        .help("The domain, as a public key or a .sol name"),
+                )
+                .arg(
+                    Arg::new("record")
+                        .long("record")
+                        .takes_value(true)
+                        .required(true)
+                        .help("The record name, e.g. TXT"),
+                ),
+        )
+        .subcommand(
+            Command::new("build")
+                .about("Build a transaction for an instruction of the program")
+                .subcommand_required(true)
+                .subcommands(build::instruction_commands()),
+        )
+        .subcommand(
+            Command::new("decode-account")
+                .about("Decode a record account from a `solana account --output json` dump")
+                .arg(
+                    Arg::new("file")
+                        .takes_value(true)
+                        .required(true)
+                        .help("The path of the JSON dump, `-` for the standard input"),
+                ),
+        )
+        .get_matches();
+
+    match matches.subcommand() {
+        Some(("record-key", matches)) => {
+            let domain = keys::parse_domain(matches.value_of("domain").unwrap())?;
+            let record = keys::parse_record(matches.value_of("record").unwrap())?;
+            println!("{}", keys::get_record_key(&domain, &record));
+        }
+        Some(("build", matches)) => {
+            let (name, matches) = matches.subcommand().unwrap();
+            let instructions = build::build_instructions(name, matches)?;
+            let keypairs =
+                transaction::read_keypairs(matches.values_of("keypair").into_iter().flatten())?;
+            let fee_payer = utils::parse_pubkey(matches.value_of("fee-payer").unwrap())?;
+            let transaction = transaction::build_transaction(
+                &instructions,
+                &fee_payer,
+                matches.value_of("blockhash").unwrap(),
+                &keypairs,
+            )?;
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&transaction::transaction_json(&transaction)?)?
+            );
+        }
+        Some(("decode-account", matches)) => {
+            let path = matches.value_of("file").unwrap();
+            let dump = if path == "-" {
+                std::io::read_to_string(std::io::stdin())?
+            } else {
+                fs::read_to_string(path).with_context(|| format!("Failed to read {}", path))?
+            };
+            let dump = serde_json::from_str(&dump).context("Invalid JSON")?;
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&account::decode_record_account(&dump)?)?
+            );
+        }
+        _ => unreachable!(),
+    }
+
+    Ok(())
+}
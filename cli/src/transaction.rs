@@ -0,0 +1,78 @@
+//! Assembles and signs transactions without an RPC connection
+
+use {
+    anyhow::{anyhow, Context, Result},
+    base64::{engine::general_purpose::STANDARD, Engine},
+    serde_json::{json, Value},
+    solana_sdk::{
+        hash::Hash,
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{read_keypair_file, Keypair, Signature},
+        signer::Signer,
+        transaction::Transaction,
+    },
+    std::str::FromStr,
+};
+
+pub fn read_keypairs<'a>(paths: impl Iterator<Item = &'a str>) -> Result<Vec<Keypair>> {
+    paths
+        .map(|path| {
+            read_keypair_file(path).map_err(|e| anyhow!("Failed to read keypair {}: {}", path, e))
+        })
+        .collect()
+}
+
+/// Builds the transaction against `blockhash` and signs it with the given keypairs
+///
+/// The transaction is partially signed when some of its signers are missing
+pub fn build_transaction(
+    instructions: &[Instruction],
+    fee_payer: &Pubkey,
+    blockhash: &str,
+    keypairs: &[Keypair],
+) -> Result<Transaction> {
+    let blockhash = Hash::from_str(blockhash).context("Invalid blockhash")?;
+    let mut transaction = Transaction::new_with_payer(instructions, Some(fee_payer));
+    transaction.message.recent_blockhash = blockhash;
+
+    let signer_keys = transaction.message.signer_keys();
+    for keypair in keypairs {
+        if !signer_keys.contains(&&keypair.pubkey()) {
+            return Err(anyhow!(
+                "{} is not a signer of the transaction",
+                keypair.pubkey()
+            ));
+        }
+    }
+    let signers = keypairs.iter().collect::<Vec<_>>();
+    transaction
+        .try_partial_sign(&signers, blockhash)
+        .context("Failed to sign the transaction")?;
+
+    Ok(transaction)
+}
+
+/// Serializes the transaction along with the signatures which are still required
+pub fn transaction_json(transaction: &Transaction) -> Result<Value> {
+    let wire_transaction = bincode::serialize(transaction)?;
+    let signers = transaction
+        .message
+        .signer_keys()
+        .into_iter()
+        .zip(&transaction.signatures)
+        .map(|(key, signature)| {
+            json!({
+                "pubkey": key.to_string(),
+                "signature": (*signature != Signature::default()).then(|| signature.to_string()),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Ok(json!({
+        "transaction": STANDARD.encode(wire_transaction),
+        "message": STANDARD.encode(transaction.message_data()),
+        "signers": signers,
+        "complete": transaction.is_signed(),
+    }))
+}
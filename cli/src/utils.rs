@@ -0,0 +1,21 @@
+use {
+    anyhow::{anyhow, Context, Result},
+    solana_sdk::pubkey::Pubkey,
+    std::{convert::TryInto, str::FromStr},
+};
+
+pub fn parse_pubkey(pubkey: &str) -> Result<Pubkey> {
+    Pubkey::from_str(pubkey).with_context(|| format!("Invalid public key {}", pubkey))
+}
+
+/// Parses hexadecimal bytes, with or without a `0x` prefix
+pub fn parse_hex(bytes: &str) -> Result<Vec<u8>> {
+    let bytes = bytes.strip_prefix("0x").unwrap_or(bytes);
+    hex::decode(bytes).with_context(|| format!("Invalid hexadecimal bytes {}", bytes))
+}
+
+pub fn parse_fixed_hex<const N: usize>(bytes: &str) -> Result<[u8; N]> {
+    parse_hex(bytes)?
+        .try_into()
+        .map_err(|bytes: Vec<u8>| anyhow!("Expected {} bytes, got {}", N, bytes.len()))
+}
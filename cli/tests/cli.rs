@@ -0,0 +1,209 @@
+use {
+    base64::{engine::general_purpose::STANDARD, Engine},
+    serde_json::{json, Value},
+    sns_records::{
+        instruction::{decode, DecodedInstruction},
+        state::{record_header::RecordHeader, validation::Validation},
+        utils::get_record_key_and_seeds,
+    },
+    solana_sdk::{
+        hash::Hash,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        signature::{write_keypair_file, Keypair},
+        signer::Signer,
+        transaction::Transaction,
+    },
+    spl_name_service::state::NameRecordHeader,
+    std::{env, fs, process::Command},
+};
+
+fn run(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_sns-records"))
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn run_json(args: &[&str]) -> Value {
+    serde_json::from_str(&run(args)).unwrap()
+}
+
+#[test]
+fn test_record_key() {
+    let domain = Pubkey::new_unique();
+    let (record_key, _) = get_record_key_and_seeds(&domain, "TXT");
+    let output = run(&[
+        "record-key",
+        "--domain",
+        &domain.to_string(),
+        "--record",
+        "TXT",
+    ]);
+    assert_eq!(output.trim(), record_key.to_string());
+}
+
+#[test]
+fn test_misspelled_record() {
+    let output = Command::new(env!("CARGO_BIN_EXE_sns-records"))
+        .args([
+            "record-key",
+            "--domain",
+            &Pubkey::new_unique().to_string(),
+            "--record",
+            "SoL",
+        ])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("did you mean SOL?"));
+
+    // Custom record names are accepted
+    run(&[
+        "record-key",
+        "--domain",
+        &Pubkey::new_unique().to_string(),
+        "--record",
+        "my-custom-record",
+    ]);
+}
+
+#[test]
+fn test_build_transaction() {
+    let owner = Keypair::new();
+    let domain = Pubkey::new_unique();
+    let blockhash = Hash::new_unique();
+    let (record_key, _) = get_record_key_and_seeds(&domain, "TXT");
+    let args = [
+        "build",
+        "edit-record",
+        "--fee-payer",
+        &owner.pubkey().to_string(),
+        "--domain",
+        &domain.to_string(),
+        "--blockhash",
+        &blockhash.to_string(),
+        "--record",
+        "TXT",
+        "--content",
+        "hello world",
+    ];
+
+    ////
+    // Unsigned transaction
+    ////
+    let output = run_json(&args);
+    assert_eq!(output["complete"], json!(false));
+    assert_eq!(
+        output["signers"],
+        json!([{ "pubkey": owner.pubkey().to_string(), "signature": null }])
+    );
+    let transaction: Transaction = bincode::deserialize(
+        &STANDARD
+            .decode(output["transaction"].as_str().unwrap())
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(transaction.message.recent_blockhash, blockhash);
+    assert_eq!(
+        STANDARD
+            .decode(output["message"].as_str().unwrap())
+            .unwrap(),
+        transaction.message_data()
+    );
+
+    let instruction = &transaction.message.instructions[0];
+    let keys = instruction
+        .accounts
+        .iter()
+        .map(|index| transaction.message.account_keys[*index as usize])
+        .collect::<Vec<_>>();
+    match decode(
+        &transaction.message.account_keys[instruction.program_id_index as usize],
+        &keys,
+        &instruction.data,
+    )
+    .unwrap()
+    {
+        DecodedInstruction::EditRecordV2 { accounts, params } => {
+            assert_eq!(accounts.record, &record_key);
+            assert_eq!(accounts.domain, &domain);
+            assert_eq!(accounts.domain_owner, &owner.pubkey());
+            assert_eq!(params.record, "TXT");
+            assert_eq!(params.content, b"hello world");
+            assert!(params.validate_content);
+        }
+        decoded => panic!("Unexpected instruction {}", decoded.name()),
+    }
+
+    ////
+    // Signed transaction
+    ////
+    let keypair_path = env::temp_dir().join(format!("{}.json", owner.pubkey()));
+    write_keypair_file(&owner, &keypair_path).unwrap();
+    let output = run_json(&[&args[..], &["--keypair", keypair_path.to_str().unwrap()]].concat());
+    fs::remove_file(keypair_path).unwrap();
+    assert_eq!(output["complete"], json!(true));
+    let transaction: Transaction = bincode::deserialize(
+        &STANDARD
+            .decode(output["transaction"].as_str().unwrap())
+            .unwrap(),
+    )
+    .unwrap();
+    transaction.verify().unwrap();
+}
+
+#[test]
+fn test_decode_account() {
+    let domain = Pubkey::new_unique();
+    let (record_key, _) = get_record_key_and_seeds(&domain, "TXT");
+    let staleness_id = Pubkey::new_unique();
+    let content = "hello world".as_bytes();
+
+    let mut data = vec![0; NameRecordHeader::LEN];
+    NameRecordHeader {
+        parent_name: domain,
+        owner: Pubkey::new_unique(),
+        class: sns_records::central_state::KEY,
+    }
+    .pack_into_slice(&mut data);
+    let header = RecordHeader {
+        staleness_validation: Validation::Solana as u16,
+        right_of_association_validation: Validation::None as u16,
+        content_length: content.len() as u32,
+    };
+    data.extend_from_slice(bytemuck::bytes_of(&header));
+    data.extend_from_slice(staleness_id.as_ref());
+    data.extend_from_slice(content);
+
+    let dump = json!({
+        "pubkey": record_key.to_string(),
+        "account": {
+            "lamports": 1_000_000,
+            "data": [STANDARD.encode(&data), "base64"],
+            "owner": spl_name_service::ID.to_string(),
+            "executable": false,
+            "rentEpoch": 0,
+            "space": data.len(),
+        },
+    });
+    let path = env::temp_dir().join(format!("{}.json", record_key));
+    fs::write(&path, dump.to_string()).unwrap();
+    let output = run_json(&["decode-account", path.to_str().unwrap()]);
+    fs::remove_file(path).unwrap();
+
+    assert_eq!(output["pubkey"], json!(record_key.to_string()));
+    assert_eq!(output["domain"], json!(domain.to_string()));
+    assert_eq!(output["headerVersion"], json!(1));
+    assert_eq!(output["stalenessValidation"], json!("Solana"));
+    assert_eq!(output["rightOfAssociationValidation"], json!("None"));
+    assert_eq!(output["stalenessId"], json!(hex::encode(staleness_id)));
+    assert_eq!(output["roaId"], json!(""));
+    assert_eq!(output["content"], json!("hello world"));
+}
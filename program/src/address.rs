@@ -0,0 +1,253 @@
+//! Decoding of the addresses posted in the SOL, ETH, BTC and INJ records
+//!
+//! Under SNS-IP-3, an address record is only trustworthy when its RoA ID is the address of its
+//! content, see `check_address_roa`. The RoA of an address record cannot be verified for another
+//! address, see `check_validated_address_roa`
+
+use solana_program::{hash::hash, pubkey::Pubkey};
+
+use crate::{
+    error::SnsRecordsError,
+    record::RecordName,
+    state::validation::{get_validation_length, BitcoinAddressType, Validation},
+};
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32_GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+const BECH32_CHECKSUM_LEN: usize = 6;
+const BECH32_MAX_LEN: usize = 90;
+
+/// Version byte of mainnet P2PKH addresses
+const BTC_P2PKH_VERSION: u8 = 0x00;
+/// Human readable part of mainnet SegWit addresses
+const BTC_HRP: &str = "bc";
+/// Version byte, HASH160 and checksum
+const BTC_P2PKH_DECODED_LEN: usize = 25;
+const BTC_P2PKH_MAX_LEN: usize = 35;
+/// Human readable part of Injective account addresses
+const INJECTIVE_HRP: &str = "inj";
+
+/// Returns the RoA validation proving the address posted in the `record` record, if it is an
+/// address record
+pub fn address_validation(record: RecordName) -> Option<Validation> {
+    match record {
+        RecordName::Sol => Some(Validation::Solana),
+        RecordName::Eth => Some(Validation::Ethereum),
+        RecordName::Btc => Some(Validation::Bitcoin),
+        _ => None,
+    }
+}
+
+/// Decodes the content of the `record` address record into the RoA ID of its address
+///
+/// - SOL: the 32 bytes of the public key
+/// - ETH: the 20 bytes of the address
+/// - BTC: the UTF-8 encoded mainnet P2PKH or P2WPKH address
+pub fn decode_address(record: RecordName, content: &[u8]) -> Result<Vec<u8>, SnsRecordsError> {
+    let validation = address_validation(record).ok_or(SnsRecordsError::UnsupportedValidation)?;
+    match validation {
+        Validation::Bitcoin => {
+            let address =
+                std::str::from_utf8(content).map_err(|_| SnsRecordsError::InvalidAddress)?;
+            let (address_type, pubkey_hash) = decode_bitcoin_address(address)?;
+            Ok([&[address_type as u8][..], &pubkey_hash].concat())
+        }
+        _ => {
+            let expected_len = get_validation_length(validation);
+            if content.len() != expected_len as usize {
+                return Err(SnsRecordsError::InvalidAddress);
+            }
+            Ok(content.to_vec())
+        }
+    }
+}
+
+/// Checks that the RoA of the `record` address record is verified for the address of its content
+pub fn check_address_roa(
+    record: RecordName,
+    roa_validation: Validation,
+    roa_id: &[u8],
+    content: &[u8],
+) -> Result<(), SnsRecordsError> {
+    let expected_validation =
+        address_validation(record).ok_or(SnsRecordsError::UnsupportedValidation)?;
+    if roa_validation != expected_validation || decode_address(record, content)? != roa_id {
+        return Err(SnsRecordsError::AddressRoaMismatch);
+    }
+    Ok(())
+}
+
+/// Checks the `roa_id` RoA about to be verified with `roa_validation` on the `record_key` record
+/// of `domain`
+///
+/// The RoA of the address record of `roa_validation` must be the address of its content, other
+/// records are left unchecked. The record must be a child of `domain`.
+pub fn check_validated_address_roa(
+    domain: &Pubkey,
+    record_key: &Pubkey,
+    roa_validation: Validation,
+    roa_id: &[u8],
+    content: &[u8],
+) -> Result<(), SnsRecordsError> {
+    let address_record = [RecordName::Sol, RecordName::Eth, RecordName::Btc]
+        .iter()
+        .copied()
+        .find(|record| address_validation(*record) == Some(roa_validation));
+    match address_record {
+        Some(record) if record.key(domain) == *record_key => {
+            check_address_roa(record, roa_validation, roa_id, content)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Decodes a mainnet P2PKH or P2WPKH address into its type and the HASH160 of its public key
+pub fn decode_bitcoin_address(
+    address: &str,
+) -> Result<(BitcoinAddressType, [u8; 20]), SnsRecordsError> {
+    let (address_type, pubkey_hash) = if address.to_ascii_lowercase().starts_with(BTC_HRP) {
+        (BitcoinAddressType::P2wpkh, decode_segwit_v0(address))
+    } else {
+        (BitcoinAddressType::P2pkh, decode_p2pkh(address))
+    };
+    Ok((
+        address_type,
+        pubkey_hash.ok_or(SnsRecordsError::InvalidAddress)?,
+    ))
+}
+
+pub(crate) fn decode_base58(input: &str) -> Option<Vec<u8>> {
+    // Little endian big integer
+    let mut bytes: Vec<u8> = Vec::new();
+    for c in input.bytes() {
+        let mut carry = BASE58_ALPHABET.iter().position(|a| *a == c)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += *byte as u32 * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    // Leading ones encode leading zeros
+    let zeros = input
+        .bytes()
+        .take_while(|c| *c == BASE58_ALPHABET[0])
+        .count();
+    bytes.resize(bytes.len() + zeros, 0);
+    bytes.reverse();
+    Some(bytes)
+}
+
+/// Base58Check: version byte, HASH160, and the first 4 bytes of its double SHA-256
+fn decode_p2pkh(address: &str) -> Option<[u8; 20]> {
+    if address.len() > BTC_P2PKH_MAX_LEN {
+        return None;
+    }
+    let decoded = decode_base58(address)?;
+    if decoded.len() != BTC_P2PKH_DECODED_LEN {
+        return None;
+    }
+    let (payload, checksum) = decoded.split_at(21);
+    if &hash(hash(payload).as_ref()).as_ref()[..4] != checksum {
+        return None;
+    }
+    let (version, pubkey_hash) = payload.split_first()?;
+    if *version != BTC_P2PKH_VERSION {
+        return None;
+    }
+    let mut result = [0; 20];
+    result.copy_from_slice(pubkey_hash);
+    Some(result)
+}
+
+fn bech32_polymod(values: impl Iterator<Item = u8>) -> u32 {
+    let mut checksum = 1u32;
+    for value in values {
+        let top = checksum >> 25;
+        checksum = ((checksum & 0x1ffffff) << 5) ^ value as u32;
+        for (i, generator) in BECH32_GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum
+}
+
+/// BIP-173: decodes the 5-bit values of a `hrp` address, without its checksum
+fn decode_bech32(address: &str, expected_hrp: &str) -> Option<Vec<u8>> {
+    if address.len() > BECH32_MAX_LEN {
+        return None;
+    }
+    // Mixed case is not allowed
+    let lowercase = address.to_ascii_lowercase();
+    if lowercase != address && address.to_ascii_uppercase() != address {
+        return None;
+    }
+    let separator = lowercase.rfind('1')?;
+    let (hrp, data) = (&lowercase[..separator], &lowercase[separator + 1..]);
+    if hrp != expected_hrp {
+        return None;
+    }
+    let mut values = data
+        .bytes()
+        .map(|c| BECH32_CHARSET.iter().position(|a| *a == c).map(|v| v as u8))
+        .collect::<Option<Vec<_>>>()?;
+    if values.len() <= BECH32_CHECKSUM_LEN {
+        return None;
+    }
+
+    let expanded_hrp = hrp
+        .bytes()
+        .map(|c| c >> 5)
+        .chain(std::iter::once(0))
+        .chain(hrp.bytes().map(|c| c & 31));
+    if bech32_polymod(expanded_hrp.chain(values.iter().copied())) != 1 {
+        return None;
+    }
+
+    values.truncate(values.len() - BECH32_CHECKSUM_LEN);
+    Some(values)
+}
+
+/// Regroups 5-bit values into a 20-byte hash, the padding must be zero and shorter than 5 bits
+fn decode_hash160(values: &[u8]) -> Option<[u8; 20]> {
+    let mut bytes = Vec::new();
+    let (mut accumulator, mut bits) = (0u32, 0u32);
+    for value in values {
+        accumulator = (accumulator << 5) | *value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((accumulator >> bits) as u8);
+            accumulator &= (1 << bits) - 1;
+        }
+    }
+    if bits >= 5 || accumulator != 0 || bytes.len() != 20 {
+        return None;
+    }
+    let mut result = [0; 20];
+    result.copy_from_slice(&bytes);
+    Some(result)
+}
+
+/// BIP-173: version 0 witness program of 20 bytes
+fn decode_segwit_v0(address: &str) -> Option<[u8; 20]> {
+    let values = decode_bech32(address, BTC_HRP)?;
+    let (version, program) = values.split_first()?;
+    if *version != 0 {
+        return None;
+    }
+    decode_hash160(program)
+}
+
+/// Decodes a bech32 Injective account address, `inj1...`, into its 20-byte address
+pub fn decode_injective_address(address: &str) -> Result<[u8; 20], SnsRecordsError> {
+    decode_bech32(address, INJECTIVE_HRP)
+        .and_then(|values| decode_hash160(&values))
+        .ok_or(SnsRecordsError::InvalidAddress)
+}
@@ -0,0 +1,220 @@
+//! Decodes the instructions of the program into their accounts and parameters
+//!
+//! The accounts are matched to the fields of the `Accounts` structure of each instruction, in
+//! the order of the table documented on `ProgramInstruction`. Trailing accounts which are not
+//! read by an instruction are ignored, as they are by the program.
+
+use {
+    crate::instruction::{
+        allocate_and_post_record, allocate_record, batch_records, begin_upload,
+        check_address_record, delete_record, edit_record, edit_record_preserve_staleness,
+        finalize_upload, migrate_record, patch_record, unverify_roa, validate_bitcoin_signature,
+        validate_ed25519_signature, validate_ethereum_signature, validate_nostr_signature,
+        validate_passkey_signature, validate_signature, validate_solana_signature, write_chunk,
+        write_roa, xchain_record_operation, ProgramInstruction,
+    },
+    borsh::BorshDeserialize,
+    num_traits::FromPrimitive,
+    solana_program::{instruction::Instruction, program_error::ProgramError, pubkey::Pubkey},
+    std::slice::Iter,
+};
+
+fn next_key<'a>(keys: &mut Iter<'a, Pubkey>) -> Result<&'a Pubkey, ProgramError> {
+    keys.next().ok_or(ProgramError::NotEnoughAccountKeys)
+}
+
+/// The parameters of an instruction, `Params` unless the entry names them
+macro_rules! decoded_params {
+    ($module:ident) => {
+        $module::Params
+    };
+    ($module:ident $params:ident) => {
+        $module::$params
+    };
+}
+
+/// The builder of an instruction, named after its module unless the entry names it
+macro_rules! decoded_builder {
+    ($module:ident) => {
+        crate::instruction::$module
+    };
+    ($module:ident $builder:ident) => {
+        crate::instruction::$builder
+    };
+}
+
+macro_rules! decoded_instructions {
+    ($(
+        $variant:ident => $module:ident $(::$params:ident as $builder:ident)?
+            { $($field:ident),* $(; ..$rest:ident)? },
+    )*) => {
+        /// An instruction of the program with its named accounts and decoded parameters
+        pub enum DecodedInstruction<'a> {
+            $(
+                $variant {
+                    accounts: $module::Accounts<'a, Pubkey>,
+                    params: decoded_params!($module $($params)?),
+                },
+            )*
+        }
+
+        impl<'a> DecodedInstruction<'a> {
+            /// Builds the instruction back with the instruction builders
+            pub fn into_instruction(self) -> Instruction {
+                match self {
+                    $(
+                        Self::$variant { accounts, params } => {
+                            decoded_builder!($module $($builder)?)(accounts, params)
+                        }
+                    )*
+                }
+            }
+
+            /// The name of the `ProgramInstruction` variant
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant { .. } => stringify!($variant),)*
+                }
+            }
+        }
+
+        /// Decodes an instruction from its program ID, account keys and data
+        ///
+        /// The keys of an `Instruction` can be collected from its `AccountMeta` list
+        pub fn decode<'a>(
+            program_id: &Pubkey,
+            accounts: &'a [Pubkey],
+            data: &[u8],
+        ) -> Result<DecodedInstruction<'a>, ProgramError> {
+            if program_id != &crate::ID {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+            let (tag, data) = data
+                .split_first()
+                .ok_or(ProgramError::InvalidInstructionData)?;
+            let instruction =
+                FromPrimitive::from_u8(*tag).ok_or(ProgramError::InvalidInstructionData)?;
+            let keys = &mut accounts.iter();
+
+            let decoded = match instruction {
+                $(
+                    ProgramInstruction::$variant => DecodedInstruction::$variant {
+                        accounts: $module::Accounts {
+                            $($field: next_key(keys)?,)*
+                            $($rest: keys.as_slice(),)?
+                        },
+                        params: <decoded_params!($module $($params)?)>::try_from_slice(data)?,
+                    },
+                )*
+            };
+
+            Ok(decoded)
+        }
+    };
+}
+
+decoded_instructions! {
+    AllocateRecord => allocate_record {
+        system_program, spl_name_service_program, fee_payer, record, domain, domain_owner,
+        central_state
+    },
+    AllocateAndPostRecord => allocate_and_post_record {
+        system_program, spl_name_service_program, fee_payer, record, domain, domain_owner,
+        central_state
+    },
+    EditRecord => edit_record {
+        system_program, spl_name_service_program, fee_payer, record, domain, domain_owner,
+        central_state
+    },
+    ValidateSolanaSignature => validate_solana_signature {
+        system_program, spl_name_service_program, fee_payer, record, domain, domain_owner,
+        central_state, verifier
+    },
+    ValidateEthereumSignature => validate_ethereum_signature {
+        system_program, spl_name_service_program, fee_payer, record, domain, domain_owner,
+        central_state
+    },
+    DeleteRecord => delete_record {
+        system_program, spl_name_service_program, fee_payer, record, domain, domain_owner,
+        central_state
+    },
+    WriteRoa => write_roa {
+        system_program, spl_name_service_program, fee_payer, record, domain, domain_owner,
+        central_state
+    },
+    UnverifyRoa => unverify_roa {
+        system_program, spl_name_service_program, fee_payer, record, domain, central_state,
+        verifier
+    },
+    BeginUpload => begin_upload {
+        system_program, spl_name_service_program, fee_payer, record, domain, domain_owner,
+        central_state
+    },
+    WriteChunk => write_chunk {
+        system_program, spl_name_service_program, fee_payer, record, domain, domain_owner,
+        central_state
+    },
+    FinalizeUpload => finalize_upload {
+        system_program, spl_name_service_program, fee_payer, record, domain, domain_owner,
+        central_state
+    },
+    PatchRecord => patch_record {
+        system_program, spl_name_service_program, fee_payer, record, domain, domain_owner,
+        central_state
+    },
+    EditRecordPreserveStaleness => edit_record_preserve_staleness {
+        system_program, spl_name_service_program, fee_payer, record, domain, domain_owner,
+        central_state
+    },
+    BatchRecords => batch_records {
+        system_program, spl_name_service_program, fee_payer, domain, domain_owner,
+        central_state; ..records
+    },
+    ValidateEd25519Signature => validate_ed25519_signature {
+        system_program, spl_name_service_program, fee_payer, record, domain, central_state,
+        instructions_sysvar
+    },
+    ValidateBitcoinSignature => validate_bitcoin_signature {
+        system_program, spl_name_service_program, fee_payer, record, domain, domain_owner,
+        central_state
+    },
+    ValidateNostrSignature => validate_nostr_signature {
+        system_program, spl_name_service_program, fee_payer, record, domain, domain_owner,
+        central_state
+    },
+    ValidatePasskeySignature => validate_passkey_signature {
+        system_program, spl_name_service_program, fee_payer, record, domain, domain_owner,
+        central_state, instructions_sysvar
+    },
+    ValidateSignature => validate_signature {
+        system_program, spl_name_service_program, fee_payer, record, domain, domain_owner,
+        central_state
+    },
+    MigrateRecord => migrate_record {
+        system_program, spl_name_service_program, fee_payer, record, domain, domain_owner,
+        central_state
+    },
+    XChainRecordOperation => xchain_record_operation {
+        system_program, spl_name_service_program, fee_payer, record, domain, domain_owner,
+        central_state, nonce
+    },
+    CheckAddressRecord => check_address_record { record, domain },
+    ValidateEthereumSignatureV2 => validate_ethereum_signature::ParamsV2
+        as validate_ethereum_signature_v2 {
+        system_program, spl_name_service_program, fee_payer, record, domain, domain_owner,
+        central_state
+    },
+    WriteRoaV2 => write_roa::ParamsV2 as write_roa_v2 {
+        system_program, spl_name_service_program, fee_payer, record, domain, domain_owner,
+        central_state
+    },
+    AllocateAndPostRecordV2 => allocate_and_post_record::ParamsV2
+        as allocate_and_post_record_v2 {
+        system_program, spl_name_service_program, fee_payer, record, domain, domain_owner,
+        central_state
+    },
+    EditRecordV2 => edit_record::ParamsV2 as edit_record_v2 {
+        system_program, spl_name_service_program, fee_payer, record, domain, domain_owner,
+        central_state
+    },
+}
@@ -49,6 +49,51 @@ impl PrintProgramError for SnsRecordsError {
             SnsRecordsError::InvalidVerifier => msg!("Error: Invalid verifier"),
             SnsRecordsError::WrongParent => msg!("Error: Wrong parent owner"),
             SnsRecordsError::WrongClass => msg!("Error: Wrong class"),
+            SnsRecordsError::UploadNotPending => msg!("Error: Record is not pending an upload"),
+            SnsRecordsError::ContentLengthMismatch => msg!("Error: Content length mismatch"),
+            SnsRecordsError::ContentHashMismatch => msg!("Error: Content hash mismatch"),
+            SnsRecordsError::InvalidPrecompileInstruction => {
+                msg!("Error: Invalid precompile instruction")
+            }
+            SnsRecordsError::SignedMessageMismatch => msg!("Error: Signed message mismatch"),
+            SnsRecordsError::InvalidSignatureHeader => msg!("Error: Invalid signature header"),
+            SnsRecordsError::BtcAddressMismatch => msg!("Error: BTC address mismatch"),
+            SnsRecordsError::InvalidSignature => msg!("Error: Invalid signature"),
+            SnsRecordsError::InvalidWebAuthnData => msg!("Error: Invalid WebAuthn data"),
+            SnsRecordsError::InvalidRoaLength => msg!("Error: Invalid RoA ID length"),
+            SnsRecordsError::AlreadyMigrated => msg!("Error: Record is already migrated"),
+            SnsRecordsError::InvalidSignatureLength => msg!("Error: Invalid signature length"),
+            SnsRecordsError::InvalidRecoveryId => msg!("Error: Invalid recovery ID"),
+            SnsRecordsError::HighSSignature => msg!("Error: Signature has a high s value"),
+            SnsRecordsError::NotXChainDomain => msg!("Error: Domain is not owned cross-chain"),
+            SnsRecordsError::InvalidNonce => msg!("Error: Invalid nonce"),
+            SnsRecordsError::RoaMismatch => {
+                msg!("Error: RoA ID does not match the declared RoA ID")
+            }
+            SnsRecordsError::InvalidValidationTransition => {
+                msg!("Error: Invalid validation transition")
+            }
+            SnsRecordsError::InvalidAddress => {
+                msg!("Error: Invalid address")
+            }
+            SnsRecordsError::AddressRoaMismatch => {
+                msg!("Error: RoA ID does not match the address of the record")
+            }
+            SnsRecordsError::UnknownRecord => {
+                msg!("Error: Unknown record")
+            }
+            SnsRecordsError::InvalidRecordContent => {
+                msg!("Error: Invalid record content")
+            }
+            SnsRecordsError::ContentTooLarge => {
+                msg!("Error: Content is too large")
+            }
+            SnsRecordsError::ReallocLimitExceeded => {
+                msg!("Error: Chunk grows the record beyond the reallocation limit")
+            }
+            SnsRecordsError::UploadPending => {
+                msg!("Error: Record is pending an upload")
+            }
         }
     }
 }
@@ -32,6 +32,56 @@ pub enum SnsRecordsError {
     WrongParent,
     #[error("Wrong class")]
     WrongClass,
+    #[error("Record is not pending an upload")]
+    UploadNotPending,
+    #[error("Content length mismatch")]
+    ContentLengthMismatch,
+    #[error("Content hash mismatch")]
+    ContentHashMismatch,
+    #[error("Invalid precompile instruction")]
+    InvalidPrecompileInstruction,
+    #[error("Signed message mismatch")]
+    SignedMessageMismatch,
+    #[error("Invalid signature header")]
+    InvalidSignatureHeader,
+    #[error("BTC address mismatch")]
+    BtcAddressMismatch,
+    #[error("Invalid signature")]
+    InvalidSignature,
+    #[error("Invalid WebAuthn data")]
+    InvalidWebAuthnData,
+    #[error("Invalid RoA ID length")]
+    InvalidRoaLength,
+    #[error("Record is already migrated")]
+    AlreadyMigrated,
+    #[error("Invalid signature length")]
+    InvalidSignatureLength,
+    #[error("Invalid recovery ID")]
+    InvalidRecoveryId,
+    #[error("Signature has a high s value")]
+    HighSSignature,
+    #[error("Domain is not owned cross-chain")]
+    NotXChainDomain,
+    #[error("Invalid nonce")]
+    InvalidNonce,
+    #[error("RoA ID does not match the declared RoA ID")]
+    RoaMismatch,
+    #[error("Invalid validation transition")]
+    InvalidValidationTransition,
+    #[error("Invalid address")]
+    InvalidAddress,
+    #[error("RoA ID does not match the address of the record")]
+    AddressRoaMismatch,
+    #[error("Unknown record")]
+    UnknownRecord,
+    #[error("Invalid record content")]
+    InvalidRecordContent,
+    #[error("Content is too large")]
+    ContentTooLarge,
+    #[error("Chunk grows the record beyond the reallocation limit")]
+    ReallocLimitExceeded,
+    #[error("Record is pending an upload")]
+    UploadPending,
 }
 
 impl From<SnsRecordsError> for ProgramError {
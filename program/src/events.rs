@@ -0,0 +1,175 @@
+//! Structured events emitted on every record mutation
+//!
+//! Events are logged with `sol_log_data` as two fields, `EVENT_TAG` followed by the Borsh
+//! serialization of a `RecordEvent`. They appear in the transaction logs as base64 encoded
+//! `Program data: <tag> <event>` lines.
+//!
+//! Chunks written during an upload are not reported, the upload is reported when it begins and
+//! when it is finalized.
+
+use {
+    crate::{
+        error::SnsRecordsError, state::record_view::RecordView, state::validation::Validation,
+    },
+    base64::{engine::general_purpose::STANDARD, Engine},
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::AccountInfo, entrypoint::ProgramResult, hash::hash, log::sol_log_data,
+        program_pack::Pack, pubkey::Pubkey,
+    },
+    spl_name_service::state::NameRecordHeader,
+};
+
+/// Distinguishes the events of the program from other `sol_log_data` logs
+pub const EVENT_TAG: [u8; 8] = *b"sns-rcev";
+
+const DATA_LOG_PREFIX: &str = "Program data: ";
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecordEventKind {
+    Allocated,
+    Edited,
+    Deleted,
+    StalenessValidated,
+    RoaWritten,
+    RoaVerified,
+    RoaUnverified,
+    Migrated,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct RecordEvent {
+    pub kind: RecordEventKind,
+    pub record: Pubkey,
+    pub domain: Pubkey,
+    /// The record name, when the instruction provides it
+    pub record_name: Option<String>,
+    pub staleness_validation: Validation,
+    pub right_of_association_validation: Validation,
+    /// SHA-256 hash of the content
+    pub content_hash: [u8; 32],
+}
+
+impl RecordEvent {
+    /// Builds the event from the current state of the record
+    ///
+    /// `Deleted` events carry the state of the record before its deletion
+    pub fn new(
+        kind: RecordEventKind,
+        record: &AccountInfo,
+        domain: &Pubkey,
+        record_name: Option<&str>,
+    ) -> Result<Self, SnsRecordsError> {
+        let record_data = record.data.borrow();
+        let view = RecordView::new(&record_data)?;
+        Ok(Self {
+            kind,
+            record: *record.key,
+            domain: *domain,
+            record_name: record_name.map(str::to_owned),
+            staleness_validation: view.staleness_validation(),
+            right_of_association_validation: view.right_of_association_validation(),
+            content_hash: hash(view.content()).to_bytes(),
+        })
+    }
+
+    pub fn emit(&self) -> ProgramResult {
+        let data = self.try_to_vec()?;
+        sol_log_data(&[&EVENT_TAG, &data]);
+        Ok(())
+    }
+
+    /// Decodes the fields of a `sol_log_data` log, returns `None` if they are not an event
+    pub fn decode(fields: &[&[u8]]) -> Option<Self> {
+        match fields {
+            [tag, data] if *tag == EVENT_TAG => Self::try_from_slice(data).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// Emits an event from the current state of the record
+pub fn emit(
+    kind: RecordEventKind,
+    record: &AccountInfo,
+    domain: &Pubkey,
+    record_name: Option<&str>,
+) -> ProgramResult {
+    RecordEvent::new(kind, record, domain, record_name)?.emit()
+}
+
+/// Emits the `Deleted` event of a record before its deletion
+///
+/// Malformed records can be deleted, their event carries no validation and the hash of their raw
+/// data
+pub fn emit_deleted(
+    record: &AccountInfo,
+    domain: &Pubkey,
+    record_name: Option<&str>,
+) -> ProgramResult {
+    let event = RecordEvent::new(RecordEventKind::Deleted, record, domain, record_name)
+        .unwrap_or_else(|_| RecordEvent {
+            kind: RecordEventKind::Deleted,
+            record: *record.key,
+            domain: *domain,
+            record_name: record_name.map(str::to_owned),
+            staleness_validation: Validation::None,
+            right_of_association_validation: Validation::None,
+            content_hash: hash(
+                record
+                    .data
+                    .borrow()
+                    .get(NameRecordHeader::LEN..)
+                    .unwrap_or_default(),
+            )
+            .to_bytes(),
+        });
+    event.emit()
+}
+
+/// Decodes the events emitted by the program from the logs of a transaction
+///
+/// Data logged by other programs, including the ones invoked by this program, is skipped
+pub fn decode_logs<S: AsRef<str>>(logs: &[S]) -> Vec<RecordEvent> {
+    let program_id = crate::ID.to_string();
+    let mut invoke_stack: Vec<bool> = vec![];
+    let mut events = vec![];
+
+    for log in logs {
+        let log = log.as_ref();
+        if let Some(data) = log.strip_prefix(DATA_LOG_PREFIX) {
+            if invoke_stack.last() != Some(&true) {
+                continue;
+            }
+            let fields = data
+                .split(' ')
+                .map(|field| STANDARD.decode(field))
+                .collect::<Result<Vec<_>, _>>();
+            if let Some(event) = fields.ok().and_then(|fields| {
+                RecordEvent::decode(&fields.iter().map(Vec::as_slice).collect::<Vec<_>>())
+            }) {
+                events.push(event);
+            }
+            continue;
+        }
+
+        let mut words = log.split(' ');
+        if let (Some("Program"), Some(id), Some(status)) =
+            (words.next(), words.next(), words.next())
+        {
+            // Skips `Program log:`, `Program return:`... logs
+            if id.ends_with(':') {
+                continue;
+            }
+            match status {
+                "invoke" => invoke_stack.push(id == program_id),
+                "success" | "failed:" => {
+                    invoke_stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    events
+}
@@ -1,6 +1,10 @@
+pub use crate::decoder::{decode, DecodedInstruction};
 pub use crate::processor::{
-    allocate_and_post_record, allocate_record, delete_record, edit_record, unverify_roa,
-    validate_ethereum_signature, validate_solana_signature, write_roa,
+    allocate_and_post_record, allocate_record, batch_records, begin_upload, check_address_record,
+    delete_record, edit_record, edit_record_preserve_staleness, finalize_upload, migrate_record,
+    patch_record, unverify_roa, validate_bitcoin_signature, validate_ed25519_signature,
+    validate_ethereum_signature, validate_nostr_signature, validate_passkey_signature,
+    validate_signature, validate_solana_signature, write_chunk, write_roa, xchain_record_operation,
 };
 use {
     bonfida_utils::InstructionsAccount,
@@ -60,7 +64,7 @@ pub enum ProgramInstruction {
     /// | 6     | ❌        | ❌      | The SNS Record central state          |
     /// | 7     | ✅        | ✅      | The RoA/Staleness verifier public key |
     ValidateSolanaSignature,
-    /// Validate and ETH signature
+    /// Validate and ETH signature over the EIP-191 message, see `ValidateEthereumSignatureV2`
     /// 
     /// | Index | Writable | Signer | Description                           |
     /// | ----------------------------------------------------------------- |
@@ -84,7 +88,7 @@ pub enum ProgramInstruction {
     /// | 5     | ✅        | ✅      | The domain owner                  |
     /// | 6     | ❌        | ❌      | The SNS Record central state      |
     DeleteRecord,
-    /// Write a RoA in the record
+    /// Write a Solana RoA in the record, see `WriteRoaV2` for the other kinds
     /// 
     /// | Index | Writable | Signer | Description                           |
     /// | ----------------------------------------------------------------- |
@@ -108,6 +112,228 @@ pub enum ProgramInstruction {
     /// | 5     | ❌        | ❌      | The SNS Record central state          |
     /// | 6     | ✅        | ✅      | The current ROA verifier              |
     UnverifyRoa,
+    /// Begin a chunked upload of the record content
+    ///
+    /// | Index | Writable | Signer | Description                       |
+    /// | ------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account        |
+    /// | 1     | ❌        | ❌      | The SPL token program account     |
+    /// | 2     | ✅        | ✅      | The fee payer account             |
+    /// | 3     | ✅        | ❌      | The record account to upload to   |
+    /// | 4     | ✅        | ❌      | The domain name owning the record |
+    /// | 5     | ✅        | ✅      | The domain owner                  |
+    /// | 6     | ❌        | ❌      | The SNS Record central state      |
+    BeginUpload,
+    /// Write a chunk of content at a given offset
+    ///
+    /// | Index | Writable | Signer | Description                       |
+    /// | ------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account        |
+    /// | 1     | ❌        | ❌      | The SPL token program account     |
+    /// | 2     | ✅        | ✅      | The fee payer account             |
+    /// | 3     | ✅        | ❌      | The record account to write to    |
+    /// | 4     | ✅        | ❌      | The domain name owning the record |
+    /// | 5     | ✅        | ✅      | The domain owner                  |
+    /// | 6     | ❌        | ❌      | The SNS Record central state      |
+    WriteChunk,
+    /// Finalize a chunked upload after checking the content length and hash
+    ///
+    /// | Index | Writable | Signer | Description                       |
+    /// | ------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account        |
+    /// | 1     | ❌        | ❌      | The SPL token program account     |
+    /// | 2     | ✅        | ✅      | The fee payer account             |
+    /// | 3     | ✅        | ❌      | The record account to finalize    |
+    /// | 4     | ✅        | ❌      | The domain name owning the record |
+    /// | 5     | ✅        | ✅      | The domain owner                  |
+    /// | 6     | ❌        | ❌      | The SNS Record central state      |
+    FinalizeUpload,
+    /// Patch the record content in place, leaving the validations untouched
+    ///
+    /// | Index | Writable | Signer | Description                       |
+    /// | ------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account        |
+    /// | 1     | ❌        | ❌      | The SPL token program account     |
+    /// | 2     | ✅        | ✅      | The fee payer account             |
+    /// | 3     | ✅        | ❌      | The record account to patch       |
+    /// | 4     | ✅        | ❌      | The domain name owning the record |
+    /// | 5     | ✅        | ✅      | The domain owner                  |
+    /// | 6     | ❌        | ❌      | The SNS Record central state      |
+    PatchRecord,
+    /// Edit the record content, keeping a staleness validation signed by the domain owner
+    ///
+    /// | Index | Writable | Signer | Description                       |
+    /// | ------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account        |
+    /// | 1     | ❌        | ❌      | The SPL token program account     |
+    /// | 2     | ✅        | ✅      | The fee payer account             |
+    /// | 3     | ✅        | ❌      | The record account to edit        |
+    /// | 4     | ✅        | ❌      | The domain name owning the record |
+    /// | 5     | ✅        | ✅      | The domain owner                  |
+    /// | 6     | ❌        | ❌      | The SNS Record central state      |
+    EditRecordPreserveStaleness,
+    /// Allocate, edit and delete several records of a domain atomically
+    ///
+    /// | Index | Writable | Signer | Description                            |
+    /// | ------------------------------------------------------------------ |
+    /// | 0     | ❌        | ❌      | The system program account             |
+    /// | 1     | ❌        | ❌      | The SPL token program account          |
+    /// | 2     | ✅        | ✅      | The fee payer account                  |
+    /// | 3     | ✅        | ❌      | The domain name owning the records     |
+    /// | 4     | ✅        | ✅      | The domain owner                       |
+    /// | 5     | ❌        | ❌      | The SNS Record central state           |
+    /// | 6..   | ✅        | ❌      | The record accounts, one per operation |
+    BatchRecords,
+    /// Validate the right of association with a signature verified by the Ed25519 program
+    ///
+    /// The Ed25519 program instruction must directly precede this instruction
+    ///
+    /// | Index | Writable | Signer | Description                       |
+    /// | ------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account        |
+    /// | 1     | ❌        | ❌      | The SPL token program account     |
+    /// | 2     | ✅        | ✅      | The fee payer account             |
+    /// | 3     | ✅        | ❌      | The record account to validate    |
+    /// | 4     | ❌        | ❌      | The domain name owning the record |
+    /// | 5     | ❌        | ❌      | The SNS Record central state      |
+    /// | 6     | ❌        | ❌      | The instructions sysvar account   |
+    ValidateEd25519Signature,
+    /// Validate a BTC signature
+    ///
+    /// | Index | Writable | Signer | Description                       |
+    /// | ------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account        |
+    /// | 1     | ❌        | ❌      | The SPL token program account     |
+    /// | 2     | ✅        | ✅      | The fee payer account             |
+    /// | 3     | ✅        | ❌      | The record account to validate    |
+    /// | 4     | ✅        | ❌      | The domain name owning the record |
+    /// | 5     | ✅        | ✅      | The domain owner                  |
+    /// | 6     | ❌        | ❌      | The SNS Record central state      |
+    ValidateBitcoinSignature,
+    /// Validate a Nostr signature
+    ///
+    /// | Index | Writable | Signer | Description                       |
+    /// | ------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account        |
+    /// | 1     | ❌        | ❌      | The SPL token program account     |
+    /// | 2     | ✅        | ✅      | The fee payer account             |
+    /// | 3     | ✅        | ❌      | The record account to validate    |
+    /// | 4     | ✅        | ❌      | The domain name owning the record |
+    /// | 5     | ✅        | ✅      | The domain owner                  |
+    /// | 6     | ❌        | ❌      | The SNS Record central state      |
+    ValidateNostrSignature,
+    /// Validate a passkey signature verified by the secp256r1 program
+    ///
+    /// The secp256r1 program instruction must directly precede this instruction
+    ///
+    /// | Index | Writable | Signer | Description                       |
+    /// | ------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account        |
+    /// | 1     | ❌        | ❌      | The SPL token program account     |
+    /// | 2     | ✅        | ✅      | The fee payer account             |
+    /// | 3     | ✅        | ❌      | The record account to validate    |
+    /// | 4     | ✅        | ❌      | The domain name owning the record |
+    /// | 5     | ✅        | ✅      | The domain owner                  |
+    /// | 6     | ❌        | ❌      | The SNS Record central state      |
+    /// | 7     | ❌        | ❌      | The instructions sysvar account   |
+    ValidatePasskeySignature,
+    /// Validate a RoA signature with the verifier of the validation
+    ///
+    /// Only `Ethereum` (EIP-191), `Bitcoin` and `Nostr` have a verifier, other validations fail
+    /// with `UnsupportedValidation`. Solana and Passkey signatures are checked by the precompiles
+    /// through the instructions sysvar, see `ValidateSolanaSignature`, `ValidateEd25519Signature`
+    /// and `ValidatePasskeySignature`.
+    ///
+    /// | Index | Writable | Signer | Description                       |
+    /// | ------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account        |
+    /// | 1     | ❌        | ❌      | The SPL token program account     |
+    /// | 2     | ✅        | ✅      | The fee payer account             |
+    /// | 3     | ✅        | ❌      | The record account to validate    |
+    /// | 4     | ✅        | ❌      | The domain name owning the record |
+    /// | 5     | ✅        | ✅      | The domain owner                  |
+    /// | 6     | ❌        | ❌      | The SNS Record central state      |
+    ValidateSignature,
+    /// Migrate the header of a record to the latest layout
+    ///
+    /// | Index | Writable | Signer | Description                       |
+    /// | ------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account        |
+    /// | 1     | ❌        | ❌      | The SPL token program account     |
+    /// | 2     | ✅        | ✅      | The fee payer account             |
+    /// | 3     | ✅        | ❌      | The record account to migrate     |
+    /// | 4     | ❌        | ❌      | The domain name owning the record |
+    /// | 5     | ❌        | ✅      | The domain owner                  |
+    /// | 6     | ❌        | ❌      | The SNS Record central state      |
+    MigrateRecord,
+    /// Edit or delete a record of a XChain domain with the authorization of its foreign owner
+    ///
+    /// | Index | Writable | Signer | Description                                                   |
+    /// | ----------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                    |
+    /// | 1     | ❌        | ❌      | The SPL token program account                                 |
+    /// | 2     | ✅        | ✅      | The fee payer account, any relayer of the authorization       |
+    /// | 3     | ✅        | ❌      | The record account to edit or delete                          |
+    /// | 4     | ✅        | ❌      | The domain name owning the record                             |
+    /// | 5     | ✅        | ❌      | The domain owner, a `XDomain` account of the SNS Warp emitter |
+    /// | 6     | ❌        | ❌      | The SNS Record central state                                  |
+    /// | 7     | ✅        | ❌      | The `XChainNonce` account of the domain                       |
+    XChainRecordOperation,
+    /// Check that the RoA of an address record is verified for the address of its content
+    ///
+    /// | Index | Writable | Signer | Description                       |
+    /// | ------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The record account to check       |
+    /// | 1     | ❌        | ❌      | The domain name owning the record |
+    CheckAddressRecord,
+    /// Validate an ETH signature over the message of the given `EthereumMessageFormat`
+    ///
+    /// | Index | Writable | Signer | Description                           |
+    /// | ----------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account            |
+    /// | 1     | ❌        | ❌      | The SPL token program account         |
+    /// | 2     | ✅        | ✅      | The fee payer account                 |
+    /// | 3     | ✅        | ❌      | The record account to create and post |
+    /// | 4     | ✅        | ❌      | The domain name owning the record     |
+    /// | 5     | ✅        | ✅      | The domain owner                      |
+    /// | 6     | ❌        | ❌      | The SNS Record central state          |
+    ValidateEthereumSignatureV2,
+    /// Write a RoA of the given unverified kind in the record
+    ///
+    /// | Index | Writable | Signer | Description                           |
+    /// | ----------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account            |
+    /// | 1     | ❌        | ❌      | The SPL token program account         |
+    /// | 2     | ✅        | ✅      | The fee payer account                 |
+    /// | 3     | ✅        | ❌      | The record account to create and post |
+    /// | 4     | ✅        | ❌      | The domain name owning the record     |
+    /// | 5     | ✅        | ✅      | The domain owner                      |
+    /// | 6     | ❌        | ❌      | The SNS Record central state          |
+    WriteRoaV2,
+    /// Allocate record account, the content is optionally validated against the record type
+    ///
+    /// | Index | Writable | Signer | Description                           |
+    /// | ----------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account            |
+    /// | 1     | ❌        | ❌      | The SPL token program account         |
+    /// | 2     | ✅        | ✅      | The fee payer account                 |
+    /// | 3     | ✅        | ❌      | The record account to create and post |
+    /// | 4     | ✅        | ❌      | The domain name owning the record     |
+    /// | 5     | ✅        | ✅      | The domain owner                      |
+    /// | 6     | ❌        | ❌      | The SNS Record central state          |
+    AllocateAndPostRecordV2,
+    /// Edit the record content, the content is optionally validated against the record type
+    ///
+    /// | Index | Writable | Signer | Description                   |
+    /// | --------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account    |
+    /// | 1     | ❌        | ❌      | The SPL token program account |
+    /// | 2     | ✅        | ✅      | The fee payer account         |
+    /// | 3     | ✅        | ❌      | The record account to edit    |
+    /// | 4     | ✅        | ❌      |                               |
+    /// | 5     | ✅        | ✅      |                               |
+    /// | 6     | ❌        | ❌      |                               |
+    EditRecordV2,
 }
 #[allow(missing_docs)]
 pub fn allocate_record(
@@ -168,3 +394,151 @@ pub fn unverify_roa(
 ) -> Instruction {
     accounts.get_instruction(crate::ID, ProgramInstruction::UnverifyRoa as u8, params)
 }
+pub fn begin_upload(
+    accounts: begin_upload::Accounts<Pubkey>,
+    params: begin_upload::Params,
+) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::BeginUpload as u8, params)
+}
+pub fn write_chunk(
+    accounts: write_chunk::Accounts<Pubkey>,
+    params: write_chunk::Params,
+) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::WriteChunk as u8, params)
+}
+pub fn finalize_upload(
+    accounts: finalize_upload::Accounts<Pubkey>,
+    params: finalize_upload::Params,
+) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::FinalizeUpload as u8, params)
+}
+pub fn patch_record(
+    accounts: patch_record::Accounts<Pubkey>,
+    params: patch_record::Params,
+) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::PatchRecord as u8, params)
+}
+pub fn edit_record_preserve_staleness(
+    accounts: edit_record_preserve_staleness::Accounts<Pubkey>,
+    params: edit_record_preserve_staleness::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        crate::ID,
+        ProgramInstruction::EditRecordPreserveStaleness as u8,
+        params,
+    )
+}
+pub fn batch_records(
+    accounts: batch_records::Accounts<Pubkey>,
+    params: batch_records::Params,
+) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::BatchRecords as u8, params)
+}
+pub fn validate_ed25519_signature(
+    accounts: validate_ed25519_signature::Accounts<Pubkey>,
+    params: validate_ed25519_signature::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        crate::ID,
+        ProgramInstruction::ValidateEd25519Signature as u8,
+        params,
+    )
+}
+pub fn validate_bitcoin_signature(
+    accounts: validate_bitcoin_signature::Accounts<Pubkey>,
+    params: validate_bitcoin_signature::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        crate::ID,
+        ProgramInstruction::ValidateBitcoinSignature as u8,
+        params,
+    )
+}
+pub fn validate_nostr_signature(
+    accounts: validate_nostr_signature::Accounts<Pubkey>,
+    params: validate_nostr_signature::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        crate::ID,
+        ProgramInstruction::ValidateNostrSignature as u8,
+        params,
+    )
+}
+pub fn validate_passkey_signature(
+    accounts: validate_passkey_signature::Accounts<Pubkey>,
+    params: validate_passkey_signature::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        crate::ID,
+        ProgramInstruction::ValidatePasskeySignature as u8,
+        params,
+    )
+}
+pub fn validate_signature(
+    accounts: validate_signature::Accounts<Pubkey>,
+    params: validate_signature::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        crate::ID,
+        ProgramInstruction::ValidateSignature as u8,
+        params,
+    )
+}
+pub fn migrate_record(
+    accounts: migrate_record::Accounts<Pubkey>,
+    params: migrate_record::Params,
+) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::MigrateRecord as u8, params)
+}
+pub fn xchain_record_operation(
+    accounts: xchain_record_operation::Accounts<Pubkey>,
+    params: xchain_record_operation::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        crate::ID,
+        ProgramInstruction::XChainRecordOperation as u8,
+        params,
+    )
+}
+pub fn check_address_record(
+    accounts: check_address_record::Accounts<Pubkey>,
+    params: check_address_record::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        crate::ID,
+        ProgramInstruction::CheckAddressRecord as u8,
+        params,
+    )
+}
+pub fn validate_ethereum_signature_v2(
+    accounts: validate_ethereum_signature::Accounts<Pubkey>,
+    params: validate_ethereum_signature::ParamsV2,
+) -> Instruction {
+    accounts.get_instruction(
+        crate::ID,
+        ProgramInstruction::ValidateEthereumSignatureV2 as u8,
+        params,
+    )
+}
+pub fn write_roa_v2(
+    accounts: write_roa::Accounts<Pubkey>,
+    params: write_roa::ParamsV2,
+) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::WriteRoaV2 as u8, params)
+}
+pub fn allocate_and_post_record_v2(
+    accounts: allocate_and_post_record::Accounts<Pubkey>,
+    params: allocate_and_post_record::ParamsV2,
+) -> Instruction {
+    accounts.get_instruction(
+        crate::ID,
+        ProgramInstruction::AllocateAndPostRecordV2 as u8,
+        params,
+    )
+}
+pub fn edit_record_v2(
+    accounts: edit_record::Accounts<Pubkey>,
+    params: edit_record::ParamsV2,
+) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::EditRecordV2 as u8, params)
+}
@@ -11,6 +11,22 @@ pub mod state;
 
 pub mod utils;
 
+pub mod ripemd160;
+
+pub mod schnorr;
+
+pub mod precompile;
+
+pub mod verifier;
+
+pub mod events;
+
+pub mod decoder;
+
+pub mod address;
+
+pub mod record;
+
 #[doc(hidden)]
 pub(crate) mod processor;
 
@@ -0,0 +1,96 @@
+//! Signature verification precompiles
+//!
+//! The Ed25519 and secp256r1 precompiles share the same instruction layout. The verified
+//! instruction must directly precede the instruction of the program reading it.
+
+use {
+    crate::error::SnsRecordsError,
+    solana_program::{
+        instruction::Instruction, program_error::ProgramError, pubkey, pubkey::Pubkey,
+    },
+};
+
+pub const SECP256R1_PROGRAM_ID: Pubkey = pubkey!("Secp256r1SigVerify1111111111111111111111111");
+
+pub const SIGNATURE_LEN: usize = 64;
+
+/// Offsets of a single signature, located after the number of signatures and a padding byte
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+
+/// Builds a precompile instruction verifying a single signature whose data is contained in the
+/// instruction itself
+pub fn new_instruction(
+    program_id: &Pubkey,
+    pubkey: &[u8],
+    signature: &[u8; SIGNATURE_LEN],
+    message: &[u8],
+) -> Instruction {
+    let pubkey_offset = SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN;
+    let signature_offset = pubkey_offset + pubkey.len();
+    let message_offset = signature_offset + SIGNATURE_LEN;
+
+    let mut data = Vec::with_capacity(message_offset + message.len());
+    // Number of signatures and padding
+    data.extend_from_slice(&[1, 0]);
+    for offset in [
+        signature_offset,
+        u16::MAX as usize,
+        pubkey_offset,
+        u16::MAX as usize,
+        message_offset,
+        message.len(),
+        u16::MAX as usize,
+    ] {
+        data.extend_from_slice(&(offset as u16).to_le_bytes());
+    }
+    data.extend_from_slice(pubkey);
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Returns the public key and the message of a precompile instruction holding a single
+/// signature whose data is contained in the instruction itself
+pub fn parse_instruction<'a>(
+    instruction: &'a Instruction,
+    program_id: &Pubkey,
+    pubkey_len: usize,
+) -> Result<(&'a [u8], &'a [u8]), ProgramError> {
+    if instruction.program_id != *program_id {
+        return Err(SnsRecordsError::InvalidPrecompileInstruction.into());
+    }
+    let data = &instruction.data;
+    if data.first() != Some(&1) {
+        return Err(SnsRecordsError::InvalidPrecompileInstruction.into());
+    }
+
+    let offsets = data
+        .get(SIGNATURE_OFFSETS_START..SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN)
+        .ok_or(SnsRecordsError::InvalidPrecompileInstruction)?
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]) as usize)
+        .collect::<Vec<_>>();
+    let (pubkey_offset, message_offset, message_len) = match offsets[..] {
+        [_, sig_ix, pubkey_offset, pubkey_ix, message_offset, message_len, message_ix]
+            if [sig_ix, pubkey_ix, message_ix] == [u16::MAX as usize; 3] =>
+        {
+            (pubkey_offset, message_offset, message_len)
+        }
+        _ => return Err(SnsRecordsError::InvalidPrecompileInstruction.into()),
+    };
+
+    let pubkey = data
+        .get(pubkey_offset..pubkey_offset + pubkey_len)
+        .ok_or(SnsRecordsError::InvalidPrecompileInstruction)?;
+    let message = data
+        .get(message_offset..message_offset + message_len)
+        .ok_or(SnsRecordsError::InvalidPrecompileInstruction)?;
+
+    Ok((pubkey, message))
+}
@@ -11,12 +11,26 @@ use crate::instruction::ProgramInstruction;
 
 pub mod allocate_and_post_record;
 pub mod allocate_record;
+pub mod batch_records;
+pub mod begin_upload;
+pub mod check_address_record;
 pub mod delete_record;
 pub mod edit_record;
+pub mod edit_record_preserve_staleness;
+pub mod finalize_upload;
+pub mod migrate_record;
+pub mod patch_record;
 pub mod unverify_roa;
+pub mod validate_bitcoin_signature;
+pub mod validate_ed25519_signature;
 pub mod validate_ethereum_signature;
+pub mod validate_nostr_signature;
+pub mod validate_passkey_signature;
+pub mod validate_signature;
 pub mod validate_solana_signature;
+pub mod write_chunk;
 pub mod write_roa;
+pub mod xchain_record_operation;
 
 pub struct Processor {}
 
@@ -27,9 +41,11 @@ impl Processor {
         instruction_data: &[u8],
     ) -> ProgramResult {
         msg!("Beginning processing");
-        let instruction = FromPrimitive::from_u8(instruction_data[0])
+        let (tag, instruction_data) = instruction_data
+            .split_first()
             .ok_or(ProgramError::InvalidInstructionData)?;
-        let instruction_data = &instruction_data[1..];
+        let instruction =
+            FromPrimitive::from_u8(*tag).ok_or(ProgramError::InvalidInstructionData)?;
         msg!("Instruction unpacked");
 
         match instruction {
@@ -41,12 +57,12 @@ impl Processor {
             ProgramInstruction::AllocateAndPostRecord => {
                 msg!("[+] Instruction: Allocate and post record");
                 let params = allocate_and_post_record::Params::try_from_slice(instruction_data)?;
-                allocate_and_post_record::process(program_id, accounts, params)?;
+                allocate_and_post_record::process(program_id, accounts, params.into())?;
             }
             ProgramInstruction::EditRecord => {
                 msg!("[+] Instruction: Edit record");
                 let params = edit_record::Params::try_from_slice(instruction_data)?;
-                edit_record::process(program_id, accounts, params)?;
+                edit_record::process(program_id, accounts, params.into())?;
             }
             ProgramInstruction::ValidateSolanaSignature => {
                 msg!("[+] Instruction: Validate Solana signature");
@@ -56,7 +72,7 @@ impl Processor {
             ProgramInstruction::ValidateEthereumSignature => {
                 msg!("[+] Instruction: Validate Ethereum signature");
                 let params = validate_ethereum_signature::Params::try_from_slice(instruction_data)?;
-                validate_ethereum_signature::process(program_id, accounts, params)?;
+                validate_ethereum_signature::process(program_id, accounts, params.into())?;
             }
             ProgramInstruction::DeleteRecord => {
                 msg!("[+] Instruction: Delete record");
@@ -66,12 +82,104 @@ impl Processor {
             ProgramInstruction::WriteRoa => {
                 msg!("[+] Instruction: Write RoA");
                 let params = write_roa::Params::try_from_slice(instruction_data)?;
-                write_roa::process(program_id, accounts, params)?;
+                write_roa::process(program_id, accounts, params.into())?;
             }
             ProgramInstruction::UnverifyRoa => {
                 msg!("[+] Instruction: Unverify RoA");
                 unverify_roa::process(program_id, accounts)?;
             }
+            ProgramInstruction::BeginUpload => {
+                msg!("[+] Instruction: Begin upload");
+                let params = begin_upload::Params::try_from_slice(instruction_data)?;
+                begin_upload::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::WriteChunk => {
+                msg!("[+] Instruction: Write chunk");
+                let params = write_chunk::Params::try_from_slice(instruction_data)?;
+                write_chunk::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::FinalizeUpload => {
+                msg!("[+] Instruction: Finalize upload");
+                let params = finalize_upload::Params::try_from_slice(instruction_data)?;
+                finalize_upload::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::PatchRecord => {
+                msg!("[+] Instruction: Patch record");
+                let params = patch_record::Params::try_from_slice(instruction_data)?;
+                patch_record::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::EditRecordPreserveStaleness => {
+                msg!("[+] Instruction: Edit record preserving staleness");
+                let params =
+                    edit_record_preserve_staleness::Params::try_from_slice(instruction_data)?;
+                edit_record_preserve_staleness::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::BatchRecords => {
+                msg!("[+] Instruction: Batch records");
+                let params = batch_records::Params::try_from_slice(instruction_data)?;
+                batch_records::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::ValidateEd25519Signature => {
+                msg!("[+] Instruction: Validate Ed25519 signature");
+                let params = validate_ed25519_signature::Params::try_from_slice(instruction_data)?;
+                validate_ed25519_signature::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::ValidateBitcoinSignature => {
+                msg!("[+] Instruction: Validate BTC signature");
+                let params = validate_bitcoin_signature::Params::try_from_slice(instruction_data)?;
+                validate_bitcoin_signature::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::ValidateNostrSignature => {
+                msg!("[+] Instruction: Validate Nostr signature");
+                let params = validate_nostr_signature::Params::try_from_slice(instruction_data)?;
+                validate_nostr_signature::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::ValidatePasskeySignature => {
+                msg!("[+] Instruction: Validate passkey signature");
+                let params = validate_passkey_signature::Params::try_from_slice(instruction_data)?;
+                validate_passkey_signature::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::ValidateSignature => {
+                msg!("[+] Instruction: Validate signature");
+                let params = validate_signature::Params::try_from_slice(instruction_data)?;
+                validate_signature::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::MigrateRecord => {
+                msg!("[+] Instruction: Migrate record");
+                let params = migrate_record::Params::try_from_slice(instruction_data)?;
+                migrate_record::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::XChainRecordOperation => {
+                msg!("[+] Instruction: XChain record operation");
+                let params = xchain_record_operation::Params::try_from_slice(instruction_data)?;
+                xchain_record_operation::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::CheckAddressRecord => {
+                msg!("[+] Instruction: Check address record");
+                let params = check_address_record::Params::try_from_slice(instruction_data)?;
+                check_address_record::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::ValidateEthereumSignatureV2 => {
+                msg!("[+] Instruction: Validate Ethereum signature V2");
+                let params =
+                    validate_ethereum_signature::ParamsV2::try_from_slice(instruction_data)?;
+                validate_ethereum_signature::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::WriteRoaV2 => {
+                msg!("[+] Instruction: Write RoA V2");
+                let params = write_roa::ParamsV2::try_from_slice(instruction_data)?;
+                write_roa::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::AllocateAndPostRecordV2 => {
+                msg!("[+] Instruction: Allocate and post record V2");
+                let params = allocate_and_post_record::ParamsV2::try_from_slice(instruction_data)?;
+                allocate_and_post_record::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::EditRecordV2 => {
+                msg!("[+] Instruction: Edit record V2");
+                let params = edit_record::ParamsV2::try_from_slice(instruction_data)?;
+                edit_record::process(program_id, accounts, params)?;
+            }
         }
 
         Ok(())
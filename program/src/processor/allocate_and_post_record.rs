@@ -3,6 +3,8 @@
 use bonfida_utils::checks::check_account_owner;
 
 use crate::{
+    events::{self, RecordEventKind},
+    record::{check_record_content, RecordName},
     state::{record_header::RecordHeader, validation::Validation},
     utils::{check_domain_owner, get_record_key_and_seeds},
 };
@@ -14,7 +16,6 @@ use {
         BorshSize, InstructionsAccount,
     },
     borsh::{BorshDeserialize, BorshSerialize},
-    // sns_sdk::record::Record,
     solana_program::{
         account_info::{next_account_info, AccountInfo},
         entrypoint::ProgramResult,
@@ -24,12 +25,54 @@ use {
     },
 };
 
+/// The content is never validated, see `ParamsV2` to validate it
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 pub struct Params {
     pub record: String,
     pub content: Vec<u8>,
 }
 
+impl Params {
+    pub fn new(record: RecordName, content: Vec<u8>) -> Self {
+        Self {
+            record: record.into(),
+            content,
+        }
+    }
+}
+
+/// The parameters of `AllocateAndPostRecordV2`
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct ParamsV2 {
+    pub record: String,
+    pub content: Vec<u8>,
+    /// Whether to check the content against the type of the record, see `RecordName::check_content`
+    ///
+    /// The content of custom records is never checked
+    pub validate_content: bool,
+}
+
+impl From<Params> for ParamsV2 {
+    fn from(params: Params) -> Self {
+        Self {
+            record: params.record,
+            content: params.content,
+            validate_content: false,
+        }
+    }
+}
+
+impl ParamsV2 {
+    /// The content of the typed record is validated
+    pub fn new(record: RecordName, content: Vec<u8>) -> Self {
+        Self {
+            record: record.into(),
+            content,
+            validate_content: true,
+        }
+    }
+}
+
 #[derive(InstructionsAccount)]
 pub struct Accounts<'a, T> {
     /// The system program account
@@ -88,10 +131,22 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
     }
 }
 
-pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: ParamsV2) -> ProgramResult {
     let accounts = Accounts::parse(accounts)?;
     check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
 
+    allocate_and_post(&accounts, params)
+}
+
+/// Allocates and posts the record, the domain owner is expected to be checked by the caller
+pub(crate) fn allocate_and_post(
+    accounts: &Accounts<AccountInfo>,
+    params: ParamsV2,
+) -> ProgramResult {
+    if params.validate_content {
+        check_record_content(&params.record, &params.content)?;
+    }
+
     let hashed = crate::utils::get_hashed_name(params.record.as_str());
     let (key, _) = get_record_key_and_seeds(accounts.domain.key, &params.record);
     check_account_key(accounts.record, &key)?;
@@ -117,5 +172,12 @@ pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -
     )?;
     cpi::edit_record(&data, 0, accounts.record, accounts.central_state)?;
 
+    events::emit(
+        RecordEventKind::Allocated,
+        accounts.record,
+        accounts.domain.key,
+        Some(&params.record),
+    )?;
+
     Ok(())
 }
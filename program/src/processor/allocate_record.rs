@@ -3,6 +3,9 @@
 use bonfida_utils::checks::check_account_owner;
 
 use crate::{
+    error::SnsRecordsError,
+    events::{self, RecordEventKind},
+    record::RecordName,
     state::record_header::RecordHeader,
     utils::{check_domain_owner, get_record_key_and_seeds},
 };
@@ -14,7 +17,6 @@ use {
         BorshSize, InstructionsAccount,
     },
     borsh::{BorshDeserialize, BorshSerialize},
-    // sns_sdk::record::Record,
     solana_program::{
         account_info::{next_account_info, AccountInfo},
         entrypoint::ProgramResult,
@@ -32,6 +34,15 @@ pub struct Params {
     pub record: String,
 }
 
+impl Params {
+    pub fn new(record: RecordName, content_length: u32) -> Self {
+        Self {
+            content_length,
+            record: record.into(),
+        }
+    }
+}
+
 #[derive(InstructionsAccount)]
 pub struct Accounts<'a, T> {
     /// The system program account
@@ -100,7 +111,10 @@ pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -
     check_account_key(accounts.record, &key)?;
 
     cpi::allocate_record(
-        params.content_length + RecordHeader::LEN as u32,
+        params
+            .content_length
+            .checked_add(RecordHeader::LEN as u32)
+            .ok_or(SnsRecordsError::NumericalOverflow)?,
         &hashed,
         accounts.record,
         accounts.fee_payer,
@@ -110,5 +124,12 @@ pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -
         accounts.system_program,
     )?;
 
+    events::emit(
+        RecordEventKind::Allocated,
+        accounts.record,
+        accounts.domain.key,
+        Some(&params.record),
+    )?;
+
     Ok(())
 }
@@ -0,0 +1,218 @@
+//! Allocate, edit and delete several records of a domain atomically
+
+use bonfida_utils::checks::check_account_owner;
+
+use crate::{
+    processor::{allocate_and_post_record, delete_record, edit_record},
+    utils::{check_domain_owner, get_record_key_and_seeds},
+};
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub enum Operation {
+    /// See `allocate_and_post_record::ParamsV2`
+    AllocateAndPost {
+        record: String,
+        content: Vec<u8>,
+        validate_content: bool,
+    },
+    /// See `edit_record::ParamsV2`
+    Edit {
+        record: String,
+        content: Vec<u8>,
+        validate_content: bool,
+    },
+    Delete {
+        record: String,
+    },
+}
+
+impl Operation {
+    pub fn record(&self) -> &str {
+        match self {
+            Operation::AllocateAndPost { record, .. }
+            | Operation::Edit { record, .. }
+            | Operation::Delete { record } => record,
+        }
+    }
+}
+
+impl BorshSize for Operation {
+    fn borsh_len(&self) -> usize {
+        1 + match self {
+            Operation::AllocateAndPost {
+                record, content, ..
+            }
+            | Operation::Edit {
+                record, content, ..
+            } => record.borsh_len() + content.borsh_len() + 1,
+            Operation::Delete { record } => record.borsh_len(),
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Params {
+    /// The operations to execute, in the order of the record accounts
+    pub operations: Vec<Operation>,
+}
+
+// Operations have variable lengths, which the derived implementation does not support
+impl BorshSize for Params {
+    fn borsh_len(&self) -> usize {
+        4 + self.operations.iter().map(|o| o.borsh_len()).sum::<usize>()
+    }
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The SPL token program account
+    pub spl_name_service_program: &'a T,
+
+    #[cons(writable, signer)]
+    /// The fee payer account
+    pub fee_payer: &'a T,
+
+    #[cons(writable)]
+    /// The domain name owning the records
+    pub domain: &'a T,
+
+    #[cons(writable, signer)]
+    /// The domain owner
+    pub domain_owner: &'a T,
+
+    /// The SNS Record central state
+    pub central_state: &'a T,
+
+    #[cons(writable)]
+    /// The record accounts, one per operation
+    pub records: &'a [T],
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            spl_name_service_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            domain: next_account_info(accounts_iter)?,
+            domain_owner: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            records: accounts_iter.as_slice(),
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.spl_name_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.central_state, &crate::central_state::KEY)?;
+
+        // Check owners
+        check_account_owner(accounts.domain, &spl_name_service::ID)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+        check_signer(accounts.domain_owner)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+
+    check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
+
+    if accounts.records.len() != params.operations.len() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    for (record, operation) in accounts.records.iter().zip(params.operations) {
+        let (key, _) = get_record_key_and_seeds(accounts.domain.key, operation.record());
+        check_account_key(record, &key)?;
+
+        match operation {
+            Operation::AllocateAndPost {
+                record: name,
+                content,
+                validate_content,
+            } => {
+                check_account_owner(record, &system_program::ID)?;
+                allocate_and_post_record::allocate_and_post(
+                    &allocate_and_post_record::Accounts {
+                        system_program: accounts.system_program,
+                        spl_name_service_program: accounts.spl_name_service_program,
+                        fee_payer: accounts.fee_payer,
+                        record,
+                        domain: accounts.domain,
+                        domain_owner: accounts.domain_owner,
+                        central_state: accounts.central_state,
+                    },
+                    allocate_and_post_record::ParamsV2 {
+                        record: name,
+                        content,
+                        validate_content,
+                    },
+                )?;
+            }
+            Operation::Edit {
+                record: name,
+                content,
+                validate_content,
+            } => {
+                check_account_owner(record, &spl_name_service::ID)?;
+                edit_record::edit(
+                    &edit_record::Accounts {
+                        system_program: accounts.system_program,
+                        spl_name_service_program: accounts.spl_name_service_program,
+                        fee_payer: accounts.fee_payer,
+                        record,
+                        domain: accounts.domain,
+                        domain_owner: accounts.domain_owner,
+                        central_state: accounts.central_state,
+                    },
+                    edit_record::ParamsV2 {
+                        record: name,
+                        content,
+                        validate_content,
+                    },
+                )?;
+            }
+            Operation::Delete { record: name } => {
+                check_account_owner(record, &spl_name_service::ID)?;
+                delete_record::delete(
+                    &delete_record::Accounts {
+                        system_program: accounts.system_program,
+                        spl_name_service_program: accounts.spl_name_service_program,
+                        fee_payer: accounts.fee_payer,
+                        record,
+                        domain: accounts.domain,
+                        domain_owner: accounts.domain_owner,
+                        central_state: accounts.central_state,
+                    },
+                    Some(&name),
+                    accounts.domain_owner,
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,172 @@
+//! Begin a chunked upload of the record content
+
+use bonfida_utils::checks::check_account_owner;
+use solana_program::program_pack::Pack;
+use spl_name_service::state::NameRecordHeader;
+
+use crate::{
+    error::SnsRecordsError,
+    events::{self, RecordEventKind},
+    record::RecordName,
+    state::record_header::VersionedRecordHeader,
+    utils::{check_domain_owner, check_domain_parent, get_record_key_and_seeds},
+};
+
+use {
+    crate::cpi,
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_instruction::MAX_PERMITTED_DATA_LENGTH,
+        system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    pub record: String,
+    /// The total length of the content that will be uploaded, the record is grown by the chunks
+    pub content_length: u32,
+}
+
+impl Params {
+    pub fn new(record: RecordName, content_length: u32) -> Self {
+        Self {
+            record: record.into(),
+            content_length,
+        }
+    }
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The SPL token program account
+    pub spl_name_service_program: &'a T,
+
+    #[cons(writable, signer)]
+    /// The fee payer account
+    pub fee_payer: &'a T,
+
+    #[cons(writable)]
+    /// The record account to upload to
+    pub record: &'a T,
+
+    #[cons(writable)]
+    /// The domain name owning the record
+    pub domain: &'a T,
+
+    #[cons(writable, signer)]
+    /// The domain owner
+    pub domain_owner: &'a T,
+
+    /// The SNS Record central state
+    pub central_state: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            spl_name_service_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            record: next_account_info(accounts_iter)?,
+            domain: next_account_info(accounts_iter)?,
+            domain_owner: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.spl_name_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.central_state, &crate::central_state::KEY)?;
+
+        // Check owners
+        check_account_owner(accounts.domain, &spl_name_service::ID)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+        check_signer(accounts.domain_owner)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+
+    check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
+
+    let (key, _) = get_record_key_and_seeds(accounts.domain.key, &params.record);
+    check_account_key(accounts.record, &key)?;
+
+    let is_allocated = accounts.record.owner != &system_program::ID;
+    let kind = if is_allocated {
+        RecordEventKind::Edited
+    } else {
+        RecordEventKind::Allocated
+    };
+
+    if is_allocated {
+        check_account_owner(accounts.record, &spl_name_service::ID)?;
+        check_domain_parent(accounts.record, accounts.domain.key)?;
+    }
+
+    // The content and the validations of the record are dropped, so is any malformed header. The
+    // pending upload is flagged in the V2 header, an empty record is never pending.
+    let header = VersionedRecordHeader::pending_upload();
+
+    let record_len = (params.content_length as u64)
+        .checked_add((NameRecordHeader::LEN + header.size()) as u64)
+        .ok_or(SnsRecordsError::NumericalOverflow)?;
+    if record_len > MAX_PERMITTED_DATA_LENGTH {
+        return Err(SnsRecordsError::ContentTooLarge.into());
+    }
+
+    // Only the header is allocated: chunks grow the account as they are written because
+    // allocations and reallocations are capped per instruction
+    if !is_allocated {
+        let hashed = crate::utils::get_hashed_name(params.record.as_str());
+        cpi::allocate_record(
+            header.size() as u32,
+            &hashed,
+            accounts.record,
+            accounts.fee_payer,
+            accounts.domain,
+            accounts.domain_owner,
+            accounts.central_state,
+            accounts.system_program,
+        )?;
+    } else {
+        if accounts.record.data_len() - NameRecordHeader::LEN != header.size() {
+            cpi::resize_record(
+                accounts.record,
+                accounts.central_state,
+                accounts.fee_payer,
+                accounts.system_program,
+                header.size() as u32,
+            )?;
+        }
+    }
+
+    cpi::edit_record(header.bytes(), 0, accounts.record, accounts.central_state)?;
+
+    events::emit(
+        kind,
+        accounts.record,
+        accounts.domain.key,
+        Some(&params.record),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+//! Check that the RoA of an address record is verified for the address of its content
+
+use crate::{
+    address::check_address_roa,
+    error::SnsRecordsError,
+    record::RecordName,
+    state::record_view::RecordView,
+    utils::{check_domain_parent, check_not_pending_upload, get_record_key_and_seeds},
+};
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_account_owner},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    /// The name of the address record: `SOL`, `ETH` or `BTC`
+    pub record: String,
+}
+
+impl Params {
+    pub fn new(record: RecordName) -> Self {
+        Self {
+            record: record.into(),
+        }
+    }
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The record account to check
+    pub record: &'a T,
+
+    /// The domain name owning the record
+    pub domain: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            record: next_account_info(accounts_iter)?,
+            domain: next_account_info(accounts_iter)?,
+        };
+
+        // Check owners
+        check_account_owner(accounts.record, &spl_name_service::ID)?;
+        check_account_owner(accounts.domain, &spl_name_service::ID)?;
+
+        Ok(accounts)
+    }
+}
+
+/// Fails unless the RoA of the record is verified for the address of its content, so that
+/// a transaction containing this instruction is rejected on mismatch
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+
+    let (record_key, _) = get_record_key_and_seeds(accounts.domain.key, &params.record);
+    check_account_key(accounts.record, &record_key)?;
+    check_domain_parent(accounts.record, accounts.domain.key)?;
+    check_not_pending_upload(accounts.record)?;
+
+    let record_data = accounts.record.data.borrow();
+    let record = RecordView::new(&record_data)?;
+
+    let address_record = params
+        .record
+        .parse()
+        .map_err(|_| SnsRecordsError::UnsupportedValidation)?;
+    check_address_roa(
+        address_record,
+        record.right_of_association_validation(),
+        record.roa_id(),
+        record.content(),
+    )?;
+
+    Ok(())
+}
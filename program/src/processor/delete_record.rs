@@ -2,7 +2,10 @@
 
 use bonfida_utils::checks::check_account_owner;
 
-use crate::utils::{check_domain_owner, check_domain_parent};
+use crate::{
+    events,
+    utils::{check_domain_owner, check_domain_parent},
+};
 
 use {
     crate::cpi,
@@ -85,13 +88,23 @@ pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], _params: Params)
     let accounts = Accounts::parse(accounts)?;
 
     check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
+
+    delete(&accounts, None, accounts.domain_owner)
+}
+
+/// Deletes the record and refunds its rent to `refund_target`, the domain owner is expected to be
+/// checked by the caller
+pub(crate) fn delete<'a>(
+    accounts: &Accounts<AccountInfo<'a>>,
+    record_name: Option<&str>,
+    refund_target: &AccountInfo<'a>,
+) -> ProgramResult {
     check_domain_parent(accounts.record, accounts.domain.key)?;
 
-    cpi::delete_record(
-        accounts.record,
-        accounts.central_state,
-        accounts.domain_owner,
-    )?;
+    // Emitted before the deletion to carry the last state of the record
+    events::emit_deleted(accounts.record, accounts.domain.key, record_name)?;
+
+    cpi::delete_record(accounts.record, accounts.central_state, refund_target)?;
 
     Ok(())
 }
@@ -5,8 +5,10 @@ use solana_program::program_pack::Pack;
 use spl_name_service::state::NameRecordHeader;
 
 use crate::{
-    state::record_header::RecordHeader,
-    utils::{check_domain_owner, check_domain_parent},
+    events::{self, RecordEventKind},
+    record::{check_record_content, RecordName},
+    state::record_header::VersionedRecordHeader,
+    utils::{check_domain_owner, check_domain_parent, get_record_key_and_seeds},
 };
 
 use {
@@ -25,12 +27,54 @@ use {
     },
 };
 
+/// The content is never validated, see `ParamsV2` to validate it
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 pub struct Params {
     pub record: String,
     pub content: Vec<u8>,
 }
 
+impl Params {
+    pub fn new(record: RecordName, content: Vec<u8>) -> Self {
+        Self {
+            record: record.into(),
+            content,
+        }
+    }
+}
+
+/// The parameters of `EditRecordV2`
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct ParamsV2 {
+    pub record: String,
+    pub content: Vec<u8>,
+    /// Whether to check the content against the type of the record, see `RecordName::check_content`
+    ///
+    /// The content of custom records is never checked
+    pub validate_content: bool,
+}
+
+impl From<Params> for ParamsV2 {
+    fn from(params: Params) -> Self {
+        Self {
+            record: params.record,
+            content: params.content,
+            validate_content: false,
+        }
+    }
+}
+
+impl ParamsV2 {
+    /// The content of the typed record is validated
+    pub fn new(record: RecordName, content: Vec<u8>) -> Self {
+        Self {
+            record: record.into(),
+            content,
+            validate_content: true,
+        }
+    }
+}
+
 #[derive(InstructionsAccount)]
 pub struct Accounts<'a, T> {
     /// The system program account
@@ -86,16 +130,30 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
     }
 }
 
-pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: ParamsV2) -> ProgramResult {
     let accounts = Accounts::parse(accounts)?;
 
     check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
+
+    // The content is validated against the type of the record named in the parameters
+    let (key, _) = get_record_key_and_seeds(accounts.domain.key, &params.record);
+    check_account_key(accounts.record, &key)?;
+
+    edit(&accounts, params)
+}
+
+/// Edits the record, the domain owner and the record key are expected to be checked by the caller
+pub(crate) fn edit(accounts: &Accounts<AccountInfo>, params: ParamsV2) -> ProgramResult {
+    if params.validate_content {
+        check_record_content(&params.record, &params.content)?;
+    }
     check_domain_parent(accounts.record, accounts.domain.key)?;
 
-    let header = RecordHeader::new(params.content.len() as u32);
+    // Only the header is read so that malformed records can be repaired, its layout is preserved
+    let header = VersionedRecordHeader::try_from_buffer(&accounts.record.data.borrow())?
+        .edit_content(params.content.len() as u32);
 
-    let header_bytes = bytemuck::bytes_of(&header);
-    let data = [header_bytes, &params.content].concat();
+    let data = [header.bytes(), &params.content].concat();
 
     if accounts.record.data_len() - NameRecordHeader::LEN != data.len() {
         cpi::resize_record(
@@ -109,5 +167,12 @@ pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -
 
     cpi::edit_record(&data, 0, accounts.record, accounts.central_state)?;
 
+    events::emit(
+        RecordEventKind::Edited,
+        accounts.record,
+        accounts.domain.key,
+        Some(&params.record),
+    )?;
+
     Ok(())
 }
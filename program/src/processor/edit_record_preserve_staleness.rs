@@ -0,0 +1,172 @@
+//! Edit the record content while keeping a staleness validation signed by the domain owner
+
+use bonfida_utils::checks::check_account_owner;
+use solana_program::program_pack::Pack;
+use spl_name_service::state::NameRecordHeader;
+
+use crate::{
+    events::{self, RecordEventKind},
+    record::RecordName,
+    state::{
+        record_header::RecordHeader,
+        record_view::RecordView,
+        validation::{Validation, ValidationEvent},
+    },
+    utils::{
+        check_domain_owner, check_domain_parent, check_not_pending_upload, get_record_key_and_seeds,
+    },
+};
+
+use {
+    crate::cpi,
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    pub record: String,
+    pub content: Vec<u8>,
+}
+
+impl Params {
+    pub fn new(record: RecordName, content: Vec<u8>) -> Self {
+        Self {
+            record: record.into(),
+            content,
+        }
+    }
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The SPL token program account
+    pub spl_name_service_program: &'a T,
+
+    #[cons(writable, signer)]
+    /// The fee payer account
+    pub fee_payer: &'a T,
+
+    #[cons(writable)]
+    /// The record account to edit
+    pub record: &'a T,
+
+    #[cons(writable)]
+    /// The domain name owning the record
+    pub domain: &'a T,
+
+    #[cons(writable, signer)]
+    /// The domain owner
+    pub domain_owner: &'a T,
+
+    /// The SNS Record central state
+    pub central_state: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            spl_name_service_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            record: next_account_info(accounts_iter)?,
+            domain: next_account_info(accounts_iter)?,
+            domain_owner: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.spl_name_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.central_state, &crate::central_state::KEY)?;
+
+        // Check owners
+        check_account_owner(accounts.record, &spl_name_service::ID)?;
+        check_account_owner(accounts.domain, &spl_name_service::ID)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+        check_signer(accounts.domain_owner)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+
+    check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
+    check_domain_parent(accounts.record, accounts.domain.key)?;
+
+    let (key, _) = get_record_key_and_seeds(accounts.domain.key, &params.record);
+    check_account_key(accounts.record, &key)?;
+    check_not_pending_upload(accounts.record)?;
+
+    let (versioned_header, staleness_id, state) = {
+        let record_data = accounts.record.data.borrow();
+        let record = RecordView::new(&record_data)?;
+        let staleness_id = record.staleness_id();
+
+        // The staleness only remains valid if it was signed by the current domain owner
+        let is_owner_staleness = matches!(record.staleness_validation(), Validation::Solana)
+            && staleness_id == accounts.domain_owner.key.as_ref();
+        // The RoA is always cleared as it was signed over the previous content
+        let event = if is_owner_staleness {
+            ValidationEvent::ClearRoa
+        } else {
+            ValidationEvent::Reset
+        };
+        (
+            record.versioned_header(),
+            is_owner_staleness.then(|| staleness_id.to_vec()),
+            record.validation_state().transition(event)?,
+        )
+    };
+
+    let mut header = RecordHeader::new(params.content.len() as u32);
+    header.set_validation_state(state);
+
+    // The layout of the header is preserved
+    let header = versioned_header.with_header(header);
+    let data = [
+        header.bytes(),
+        staleness_id.as_deref().unwrap_or_default(),
+        &params.content,
+    ]
+    .concat();
+
+    if accounts.record.data_len() - NameRecordHeader::LEN != data.len() {
+        cpi::resize_record(
+            accounts.record,
+            accounts.central_state,
+            accounts.fee_payer,
+            accounts.system_program,
+            data.len() as u32,
+        )?;
+    }
+
+    cpi::edit_record(&data, 0, accounts.record, accounts.central_state)?;
+
+    events::emit(
+        RecordEventKind::Edited,
+        accounts.record,
+        accounts.domain.key,
+        Some(&params.record),
+    )?;
+
+    Ok(())
+}
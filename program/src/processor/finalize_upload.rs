@@ -0,0 +1,135 @@
+//! Finalize a chunked upload of the record content
+
+use bonfida_utils::checks::check_account_owner;
+use solana_program::hash::hash;
+
+use crate::{
+    error::SnsRecordsError,
+    events::{self, RecordEventKind},
+    state::record_view::RecordView,
+    utils::{check_domain_owner, check_domain_parent},
+};
+
+use {
+    crate::cpi,
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    /// The expected length of the uploaded content
+    pub content_length: u32,
+    /// The expected SHA-256 hash of the uploaded content
+    pub content_hash: [u8; 32],
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The SPL token program account
+    pub spl_name_service_program: &'a T,
+
+    #[cons(writable, signer)]
+    /// The fee payer account
+    pub fee_payer: &'a T,
+
+    #[cons(writable)]
+    /// The record account to finalize
+    pub record: &'a T,
+
+    #[cons(writable)]
+    /// The domain name owning the record
+    pub domain: &'a T,
+
+    #[cons(writable, signer)]
+    /// The domain owner
+    pub domain_owner: &'a T,
+
+    /// The SNS Record central state
+    pub central_state: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            spl_name_service_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            record: next_account_info(accounts_iter)?,
+            domain: next_account_info(accounts_iter)?,
+            domain_owner: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.spl_name_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.central_state, &crate::central_state::KEY)?;
+
+        // Check owners
+        check_account_owner(accounts.record, &spl_name_service::ID)?;
+        check_account_owner(accounts.domain, &spl_name_service::ID)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+        check_signer(accounts.domain_owner)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+    let Params {
+        content_length,
+        content_hash,
+    } = params;
+
+    check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
+    check_domain_parent(accounts.record, accounts.domain.key)?;
+
+    let header = {
+        let record_data = accounts.record.data.borrow();
+        let record = RecordView::new(&record_data)?;
+        if !record.versioned_header().is_pending_upload() {
+            return Err(SnsRecordsError::UploadNotPending.into());
+        }
+
+        // A record pending an upload has no validation IDs
+        let content = record.content();
+
+        if content.len() != content_length as usize {
+            return Err(SnsRecordsError::ContentLengthMismatch.into());
+        }
+        if hash(content).to_bytes() != content_hash {
+            return Err(SnsRecordsError::ContentHashMismatch.into());
+        }
+
+        record.versioned_header().finalize_upload(content_length)
+    };
+
+    cpi::edit_record(header.bytes(), 0, accounts.record, accounts.central_state)?;
+
+    events::emit(
+        RecordEventKind::Edited,
+        accounts.record,
+        accounts.domain.key,
+        None,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+//! Migrate the header of a record to the latest layout
+
+use crate::{
+    error::SnsRecordsError,
+    events::{self, RecordEventKind},
+    state::{
+        record_header::{RecordHeaderV2, VersionedRecordHeader},
+        record_view::RecordView,
+    },
+    utils::{check_domain_owner, check_domain_parent},
+};
+
+use {
+    crate::cpi,
+    bonfida_utils::{
+        checks::{check_account_key, check_account_owner, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_program,
+    },
+    std::convert::TryInto,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The SPL token program account
+    pub spl_name_service_program: &'a T,
+
+    #[cons(writable, signer)]
+    /// The fee payer account
+    pub fee_payer: &'a T,
+
+    #[cons(writable)]
+    /// The record account to migrate
+    pub record: &'a T,
+
+    /// The domain name owning the record
+    pub domain: &'a T,
+
+    #[cons(signer)]
+    /// The domain owner
+    pub domain_owner: &'a T,
+
+    /// The SNS Record central state
+    pub central_state: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            spl_name_service_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            record: next_account_info(accounts_iter)?,
+            domain: next_account_info(accounts_iter)?,
+            domain_owner: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.spl_name_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.central_state, &crate::central_state::KEY)?;
+
+        // Check owners
+        check_account_owner(accounts.record, &spl_name_service::ID)?;
+        check_account_owner(accounts.domain, &spl_name_service::ID)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+        check_signer(accounts.domain_owner)?;
+
+        Ok(accounts)
+    }
+}
+
+/// The migration changes the layout of the record account, it is therefore authorized by the
+/// domain owner, the fee payer funds the additional rent
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], _params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+
+    check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
+    check_domain_parent(accounts.record, accounts.domain.key)?;
+
+    let data = {
+        let record_data = accounts.record.data.borrow();
+        let record = RecordView::new(&record_data)?;
+        // Only the layout changes, the validation state is copied as is
+        let header = match record.versioned_header() {
+            VersionedRecordHeader::V1(header) => RecordHeaderV2::new(header, 0),
+            VersionedRecordHeader::V2(_) => return Err(SnsRecordsError::AlreadyMigrated.into()),
+        };
+        [bytemuck::bytes_of(&header), record.data()].concat()
+    };
+
+    cpi::resize_record(
+        accounts.record,
+        accounts.central_state,
+        accounts.fee_payer,
+        accounts.system_program,
+        data.len()
+            .try_into()
+            .map_err(|_| SnsRecordsError::NumericalOverflow)?,
+    )?;
+
+    cpi::edit_record(&data, 0, accounts.record, accounts.central_state)?;
+
+    events::emit(
+        RecordEventKind::Migrated,
+        accounts.record,
+        accounts.domain.key,
+        None,
+    )?;
+
+    Ok(())
+}
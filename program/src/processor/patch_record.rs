@@ -0,0 +1,148 @@
+//! Patch the record content in place
+
+use bonfida_utils::checks::check_account_owner;
+
+use crate::{
+    error::SnsRecordsError,
+    events::{self, RecordEventKind},
+    state::record_view::RecordView,
+    utils::{check_domain_owner, check_domain_parent, check_not_pending_upload},
+};
+
+use {
+    crate::cpi,
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_program,
+    },
+    std::convert::TryInto,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Patch {
+    /// The offset of the patch in the record content
+    pub offset: u32,
+    pub data: Vec<u8>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Params {
+    pub patches: Vec<Patch>,
+}
+
+// Patches have variable lengths, which the derived implementation does not support
+impl BorshSize for Params {
+    fn borsh_len(&self) -> usize {
+        4 + self.patches.iter().map(|p| p.borsh_len()).sum::<usize>()
+    }
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The SPL token program account
+    pub spl_name_service_program: &'a T,
+
+    #[cons(writable, signer)]
+    /// The fee payer account
+    pub fee_payer: &'a T,
+
+    #[cons(writable)]
+    /// The record account to patch
+    pub record: &'a T,
+
+    #[cons(writable)]
+    /// The domain name owning the record
+    pub domain: &'a T,
+
+    #[cons(writable, signer)]
+    /// The domain owner
+    pub domain_owner: &'a T,
+
+    /// The SNS Record central state
+    pub central_state: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            spl_name_service_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            record: next_account_info(accounts_iter)?,
+            domain: next_account_info(accounts_iter)?,
+            domain_owner: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.spl_name_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.central_state, &crate::central_state::KEY)?;
+
+        // Check owners
+        check_account_owner(accounts.record, &spl_name_service::ID)?;
+        check_account_owner(accounts.domain, &spl_name_service::ID)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+        check_signer(accounts.domain_owner)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+
+    check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
+    check_domain_parent(accounts.record, accounts.domain.key)?;
+    check_not_pending_upload(accounts.record)?;
+
+    // The header and the validation IDs are left untouched, only the content is patched
+    let (content_offset, content_length) = {
+        let record_data = accounts.record.data.borrow();
+        let record = RecordView::new(&record_data)?;
+        let content_offset =
+            record.versioned_header().size() + record.staleness_id().len() + record.roa_id().len();
+        (content_offset, record.content().len())
+    };
+
+    for Patch { offset, data } in params.patches {
+        let end = (offset as usize)
+            .checked_add(data.len())
+            .ok_or(SnsRecordsError::NumericalOverflow)?;
+        if end > content_length {
+            return Err(SnsRecordsError::OutOfBound.into());
+        }
+
+        cpi::edit_record(
+            &data,
+            (content_offset + offset as usize)
+                .try_into()
+                .map_err(|_| SnsRecordsError::NumericalOverflow)?,
+            accounts.record,
+            accounts.central_state,
+        )?;
+    }
+
+    events::emit(
+        RecordEventKind::Edited,
+        accounts.record,
+        accounts.domain.key,
+        None,
+    )?;
+
+    Ok(())
+}
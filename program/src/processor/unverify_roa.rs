@@ -1,19 +1,13 @@
 //! Unverify a RoA in the record
 
-use std::convert::TryInto;
-
-use spl_name_service::state::NameRecordHeader;
-
 use crate::{
     error::SnsRecordsError,
-    state::{
-        record_header::RecordHeader,
-        validation::{get_validation_length, Validation},
-    },
+    events::{self, RecordEventKind},
+    state::{record_view::RecordView, validation::ValidationEvent},
+    utils::{check_not_pending_upload, update_roa},
 };
 
 use {
-    crate::cpi,
     bonfida_utils::{
         checks::{check_account_key, check_account_owner, check_signer},
         BorshSize, InstructionsAccount,
@@ -23,7 +17,6 @@ use {
         account_info::{next_account_info, AccountInfo},
         entrypoint::ProgramResult,
         program_error::ProgramError,
-        program_pack::Pack,
         pubkey::Pubkey,
         system_program,
     },
@@ -91,45 +84,32 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
 
 pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     let accounts = Accounts::parse(accounts)?;
+    check_not_pending_upload(accounts.record)?;
 
-    let (new_buffer, current_length, header) = {
+    {
         let record_data = accounts.record.data.borrow();
-        let mut header = RecordHeader::from_buffer(&record_data);
-        let (_, buffer) = record_data.split_at(NameRecordHeader::LEN + RecordHeader::LEN);
-
-        let mut new_buffer: Vec<u8> = vec![];
+        let record = RecordView::new(&record_data)?;
 
-        let (staleness_id, rest) = buffer
-            .split_at(get_validation_length(header.staleness_validation.try_into()?) as usize);
-        let (roa, content) = rest.split_at(get_validation_length(
-            header.right_of_association_validation.try_into()?,
-        ) as usize);
-
-        if roa != accounts.verifier.key.as_ref() {
+        if record.roa_id() != accounts.verifier.key.as_ref() {
             return Err(SnsRecordsError::InvalidVerifier.into());
         }
-
-        header.right_of_association_validation = Validation::None as u16;
-        new_buffer.extend_from_slice(staleness_id);
-        new_buffer.extend_from_slice(content);
-
-        (new_buffer, buffer.len(), header)
-    };
-
-    if new_buffer.len() != current_length {
-        cpi::resize_record(
-            accounts.record,
-            accounts.central_state,
-            accounts.fee_payer,
-            accounts.system_program,
-            (new_buffer.len() + RecordHeader::LEN) as u32,
-        )?;
     }
 
-    let header_bytes = bytemuck::bytes_of(&header);
-    let data = [header_bytes, &new_buffer].concat();
-
-    cpi::edit_record(&data, 0, accounts.record, accounts.central_state)?;
+    update_roa(
+        accounts.record,
+        accounts.central_state,
+        accounts.fee_payer,
+        accounts.system_program,
+        ValidationEvent::ClearRoa,
+        &[],
+    )?;
+
+    events::emit(
+        RecordEventKind::RoaUnverified,
+        accounts.record,
+        accounts.domain.key,
+        None,
+    )?;
 
     Ok(())
 }
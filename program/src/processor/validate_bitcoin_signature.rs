@@ -0,0 +1,256 @@
+//! Validate a BTC signature
+
+use crate::{
+    address::check_validated_address_roa,
+    error::SnsRecordsError,
+    events::{self, RecordEventKind},
+    processor::validate_ethereum_signature::{RECORD_SUFFIX, STALENESS_SUFFIX},
+    ripemd160,
+    state::validation::{get_validation_length, BitcoinAddressType, Validation, ValidationEvent},
+    utils::{check_domain_owner, check_domain_parent, check_not_pending_upload, update_roa},
+    verifier::{SignatureVerifier, SignedRecord},
+};
+
+use {
+    bonfida_utils::checks::check_account_owner,
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::hash::hash,
+    solana_program::secp256k1_recover::secp256k1_recover,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    /// The BIP-137 signature: header byte, followed by r and s
+    pub signature: Vec<u8>,
+    /// The type of the expected address, P2PKH signatures have a 27-34 header and P2WPKH ones 39-42
+    pub address_type: BitcoinAddressType,
+    /// The expected HASH160 of the BTC public key
+    pub expected_pubkey_hash: [u8; 20],
+}
+
+pub const BTC_PREFIX_BYTES: &[u8; 25] = b"\x18Bitcoin Signed Message:\n";
+
+/// Bitcoin variable length integer
+fn compact_size(n: usize) -> Vec<u8> {
+    match n {
+        0..=0xfc => vec![n as u8],
+        0xfd..=0xffff => [&[0xfd], &(n as u16).to_le_bytes()[..]].concat(),
+        _ => [&[0xfe], &(n as u32).to_le_bytes()[..]].concat(),
+    }
+}
+
+///
+/// The message to sign must contain the record public key & staleness ID
+/// It is hashed twice with SHA-256
+// +------------------------+------------------+------------------+------------------+------------------+------------------+------------------+
+// | BTC_PREFIX_BYTES       | content_length   | content          | RECORD_SUFFIX    | record_key       | STALENESS_SUFFIX | staleness_id     |
+// +------------------------+------------------+------------------+------------------+------------------+------------------+------------------+
+// | "\x18Bitcoin Signed    | Compact size of  | Hex encoded      | "\nFor record: " | Public key of    | "\nStaleness ID: " | Public key of   |
+// | Message:\n"            | (content +       | content          |                  | the record       |                  | the staleness    |
+// |                        | record_key +     |                  |                  |                  |                  |                  |
+// |                        | RECORD_SUFFIX +  |                  |                  |                  |                  |                  |
+// |                        | staleness_id +   |                  |                  |                  |                  |                  |
+// |                        | STALENESS_SUFFIX)|                  |                  |                  |                  |                  |
+// +------------------------+------------------+------------------+------------------+------------------+------------------+------------------+
+pub fn message_to_sign(content: &[u8], record_key: &Pubkey, staleness_id: &Pubkey) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    let record_key_base58 = record_key.to_string();
+    let staleness_id_base58 = staleness_id.to_string();
+    let hex_encoded_content = hex::encode(content);
+
+    let content_length = hex_encoded_content.len()
+        + record_key_base58.len()
+        + staleness_id_base58.len()
+        + RECORD_SUFFIX.len()
+        + STALENESS_SUFFIX.len();
+
+    buffer.extend_from_slice(BTC_PREFIX_BYTES);
+    buffer.extend_from_slice(&compact_size(content_length));
+    buffer.extend_from_slice(hex_encoded_content.as_bytes());
+    buffer.extend_from_slice(RECORD_SUFFIX);
+    buffer.extend_from_slice(record_key_base58.as_bytes());
+    buffer.extend_from_slice(STALENESS_SUFFIX);
+    buffer.extend_from_slice(staleness_id_base58.as_bytes());
+
+    buffer
+}
+
+/// RIPEMD-160 of the SHA-256 of the public key
+pub fn hash160(pubkey: &[u8]) -> [u8; 20] {
+    ripemd160::hash(hash(pubkey).as_ref())
+}
+
+/// Verifies BIP-137 signatures, the ID is the address type followed by the HASH160 of the public
+/// key
+pub struct BitcoinVerifier;
+
+impl SignatureVerifier for BitcoinVerifier {
+    fn id_len(&self) -> usize {
+        get_validation_length(Validation::Bitcoin) as usize
+    }
+
+    fn build_message(&self, record: &SignedRecord) -> Result<Vec<u8>, ProgramError> {
+        Ok(message_to_sign(
+            record.content,
+            record.record_key,
+            &record.staleness_key()?,
+        ))
+    }
+
+    fn verify(&self, message: &[u8], signature: &[u8], id: &[u8]) -> ProgramResult {
+        let (address_type, expected_pubkey_hash) =
+            id.split_first().ok_or(SnsRecordsError::OutOfBound)?;
+        let address_type = BitcoinAddressType::try_from_slice(&[*address_type])?;
+
+        if signature.len() != 65 {
+            return Err(SnsRecordsError::InvalidSignatureLength.into());
+        }
+
+        let hash = hash(hash(message).as_ref());
+
+        // BIP-137 header: 27-30 uncompressed P2PKH, 31-34 compressed P2PKH,
+        // 35-38 P2SH-P2WPKH and 39-42 P2WPKH, the header must match the address type
+        let signature_header = *signature.first().ok_or(SnsRecordsError::OutOfBound)?;
+        let expected_headers = match address_type {
+            BitcoinAddressType::P2pkh => 27..=34,
+            BitcoinAddressType::P2wpkh => 39..=42,
+        };
+        if !expected_headers.contains(&signature_header) {
+            return Err(SnsRecordsError::InvalidSignatureHeader.into());
+        }
+        let recovery_id = (signature_header - 27) % 4;
+        let compressed = signature_header >= 31;
+
+        let recovered_pubkey = secp256k1_recover(hash.as_ref(), recovery_id, &signature[1..])
+            .map_err(|_| SnsRecordsError::Secp256k1Recover)?;
+
+        let pubkey_hash = if compressed {
+            let (x, y) = recovered_pubkey.0.split_at(32);
+            hash160(&[&[0x02 | (y[31] & 1)], x].concat())
+        } else {
+            hash160(&[&[0x04], recovered_pubkey.0.as_ref()].concat())
+        };
+
+        if pubkey_hash != expected_pubkey_hash {
+            return Err(SnsRecordsError::BtcAddressMismatch.into());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The SPL token program account
+    pub spl_name_service_program: &'a T,
+
+    #[cons(writable, signer)]
+    /// The fee payer account
+    pub fee_payer: &'a T,
+
+    #[cons(writable)]
+    /// The record account to validate
+    pub record: &'a T,
+
+    #[cons(writable)]
+    /// The domain name owning the record
+    pub domain: &'a T,
+
+    #[cons(writable, signer)]
+    /// The domain owner
+    pub domain_owner: &'a T,
+
+    /// The SNS Record central state
+    pub central_state: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            spl_name_service_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            record: next_account_info(accounts_iter)?,
+            domain: next_account_info(accounts_iter)?,
+            domain_owner: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.spl_name_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.central_state, &crate::central_state::KEY)?;
+
+        // Check owners
+        check_account_owner(accounts.record, &spl_name_service::ID)?;
+        check_account_owner(accounts.domain, &spl_name_service::ID)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+        check_signer(accounts.domain_owner)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+    let Params {
+        signature,
+        address_type,
+        expected_pubkey_hash,
+    } = params;
+
+    check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
+    check_domain_parent(accounts.record, accounts.domain.key)?;
+    check_not_pending_upload(accounts.record)?;
+
+    let id = [&[address_type as u8], &expected_pubkey_hash[..]].concat();
+
+    {
+        let record_data = accounts.record.data.borrow();
+        let record = SignedRecord::from_record_data(accounts.record.key, &record_data)?;
+        let message = BitcoinVerifier.build_message(&record)?;
+        BitcoinVerifier.verify(&message, &signature, &id)?;
+        check_validated_address_roa(
+            accounts.domain.key,
+            accounts.record.key,
+            Validation::Bitcoin,
+            &id,
+            record.content,
+        )?;
+    }
+
+    update_roa(
+        accounts.record,
+        accounts.central_state,
+        accounts.fee_payer,
+        accounts.system_program,
+        ValidationEvent::ValidateRoa(Validation::Bitcoin),
+        &id,
+    )?;
+
+    events::emit(
+        RecordEventKind::RoaVerified,
+        accounts.record,
+        accounts.domain.key,
+        None,
+    )?;
+
+    Ok(())
+}
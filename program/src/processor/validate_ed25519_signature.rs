@@ -0,0 +1,185 @@
+//! Validate a Solana RoA with an offline Ed25519 signature
+
+use crate::{
+    address::check_validated_address_roa,
+    error::SnsRecordsError,
+    events::{self, RecordEventKind},
+    precompile,
+    state::{
+        record_view::RecordView,
+        validation::{Validation, ValidationEvent},
+    },
+    utils::{check_domain_parent, check_not_pending_upload},
+};
+
+use {
+    crate::cpi,
+    bonfida_utils::{
+        checks::{check_account_key, check_account_owner, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        ed25519_program,
+        entrypoint::ProgramResult,
+        instruction::Instruction as SolanaInstruction,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_program,
+        sysvar::instructions::{self, get_instruction_relative},
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {}
+
+pub const ED25519_PREFIX: &[u8; 16] = b"SNS Records RoA\n";
+
+const PUBKEY_LEN: usize = 32;
+
+/// The message the verifier signs offline
+// +------------------+------------+----------------------+--------------+---------+
+// | ED25519_PREFIX   | record_key | staleness_validation | staleness_id | content |
+// +------------------+------------+----------------------+--------------+---------+
+// | "SNS Records     | 32 bytes   | u16 (little endian)  | Depends on   | Record  |
+// | RoA\n"           |            |                      | validation   | content |
+// +------------------+------------+----------------------+--------------+---------+
+pub fn message_to_sign(
+    record_key: &Pubkey,
+    staleness_validation: Validation,
+    staleness_id: &[u8],
+    content: &[u8],
+) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(
+        ED25519_PREFIX.len() + PUBKEY_LEN + 2 + staleness_id.len() + content.len(),
+    );
+    buffer.extend_from_slice(ED25519_PREFIX);
+    buffer.extend_from_slice(record_key.as_ref());
+    buffer.extend_from_slice(&(staleness_validation as u16).to_le_bytes());
+    buffer.extend_from_slice(staleness_id);
+    buffer.extend_from_slice(content);
+    buffer
+}
+
+/// Builds the Ed25519 precompile instruction that must directly precede the validation
+pub fn new_ed25519_instruction(
+    verifier: &Pubkey,
+    signature: &[u8; precompile::SIGNATURE_LEN],
+    message: &[u8],
+) -> SolanaInstruction {
+    precompile::new_instruction(&ed25519_program::ID, verifier.as_ref(), signature, message)
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The SPL token program account
+    pub spl_name_service_program: &'a T,
+
+    #[cons(writable, signer)]
+    /// The fee payer account
+    pub fee_payer: &'a T,
+
+    #[cons(writable)]
+    /// The record account to validate
+    pub record: &'a T,
+
+    /// The domain name owning the record
+    pub domain: &'a T,
+
+    /// The SNS Record central state
+    pub central_state: &'a T,
+
+    /// The instructions sysvar account
+    pub instructions_sysvar: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            spl_name_service_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            record: next_account_info(accounts_iter)?,
+            domain: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            instructions_sysvar: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.spl_name_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.central_state, &crate::central_state::KEY)?;
+        check_account_key(accounts.instructions_sysvar, &instructions::ID)?;
+
+        // Check owners
+        check_account_owner(accounts.record, &spl_name_service::ID)?;
+        check_account_owner(accounts.domain, &spl_name_service::ID)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], _params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+
+    check_domain_parent(accounts.record, accounts.domain.key)?;
+    check_not_pending_upload(accounts.record)?;
+
+    let ed25519_instruction = get_instruction_relative(-1, accounts.instructions_sysvar)?;
+    let (verifier, signed_message) =
+        precompile::parse_instruction(&ed25519_instruction, &ed25519_program::ID, PUBKEY_LEN)?;
+
+    let header = {
+        let record_data = accounts.record.data.borrow();
+        let record = RecordView::new(&record_data)?;
+        // The RoA ID is declared, see `ValidationEvent::ValidateRoa`
+        let state = record
+            .validation_state()
+            .transition(ValidationEvent::ValidateRoa(Validation::Solana))?;
+
+        if record.roa_id() != verifier {
+            return Err(SnsRecordsError::InvalidVerifier.into());
+        }
+        check_validated_address_roa(
+            accounts.domain.key,
+            accounts.record.key,
+            Validation::Solana,
+            record.roa_id(),
+            record.content(),
+        )?;
+
+        let expected_message = message_to_sign(
+            accounts.record.key,
+            record.staleness_validation(),
+            record.staleness_id(),
+            record.content(),
+        );
+        if expected_message != signed_message {
+            return Err(SnsRecordsError::SignedMessageMismatch.into());
+        }
+
+        let mut header = record.header();
+        header.set_validation_state(state);
+        record.versioned_header().with_header(header)
+    };
+
+    // The buffer remains unchanged, only the header is updated
+    cpi::edit_record(header.bytes(), 0, accounts.record, accounts.central_state)?;
+
+    events::emit(
+        RecordEventKind::RoaVerified,
+        accounts.record,
+        accounts.domain.key,
+        None,
+    )?;
+
+    Ok(())
+}
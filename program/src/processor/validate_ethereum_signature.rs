@@ -1,50 +1,81 @@
 //! Validate and ETH signature
 
 use crate::{
+    address::check_validated_address_roa,
     error::SnsRecordsError,
-    state::{
-        record_header::RecordHeader,
-        validation::{get_validation_length, Validation},
-    },
-    utils::{check_domain_owner, check_domain_parent},
+    events::{self, RecordEventKind},
+    state::validation::{get_validation_length, Validation, ValidationEvent},
+    utils::{check_domain_owner, check_domain_parent, check_not_pending_upload, update_roa},
+    verifier::{SignatureVerifier, SignedRecord},
 };
 
 use {
-    crate::cpi,
     bonfida_utils::checks::check_account_owner,
     bonfida_utils::{
         checks::{check_account_key, check_signer},
         BorshSize, InstructionsAccount,
     },
     borsh::{BorshDeserialize, BorshSerialize},
-    solana_program::keccak::Hasher,
+    solana_program::keccak::{hashv, Hasher},
     solana_program::secp256k1_recover::secp256k1_recover,
     solana_program::{
         account_info::{next_account_info, AccountInfo},
         entrypoint::ProgramResult,
         program_error::ProgramError,
-        program_pack::Pack,
         pubkey::Pubkey,
         system_program,
     },
-    spl_name_service::state::NameRecordHeader,
-    std::convert::TryInto,
 };
 
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EthereumMessageFormat {
+    /// `personal_sign` message, see `message_to_sign`
+    Eip191,
+    /// Typed structured data, see `eip712_message_hash`
+    Eip712,
+}
+
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 pub struct Params {
     /// The type of validation
     pub validation: Validation,
-    /// The record enum as a string
+    /// The signature, see `normalize_signature` for the accepted encodings
     pub signature: Vec<u8>,
     /// The expected ETH public key
     pub expected_pubkey: Vec<u8>,
 }
 
+/// The parameters of `ValidateEthereumSignatureV2`, `Params` are signed with
+/// `EthereumMessageFormat::Eip191`
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct ParamsV2 {
+    /// The type of validation
+    pub validation: Validation,
+    /// The signature, see `normalize_signature` for the accepted encodings
+    pub signature: Vec<u8>,
+    /// The expected ETH public key
+    pub expected_pubkey: Vec<u8>,
+    /// The format of the signed message
+    pub format: EthereumMessageFormat,
+}
+
+impl From<Params> for ParamsV2 {
+    fn from(params: Params) -> Self {
+        Self {
+            validation: params.validation,
+            signature: params.signature,
+            expected_pubkey: params.expected_pubkey,
+            format: EthereumMessageFormat::Eip191,
+        }
+    }
+}
+
 pub const ETH_PREFIX_BYTES: &[u8; 26] = b"\x19Ethereum Signed Message:\n";
 pub const RECORD_SUFFIX: &[u8; 13] = b"\nFor record: ";
 pub const STALENESS_SUFFIX: &[u8; 15] = b"\nStaleness ID: ";
+pub const XCHAIN_STALENESS_SUFFIX: &[u8; 22] = b"\nXChain staleness ID: ";
 
+/// The EIP-191 (`personal_sign`) message signed by the Ethereum wallet, including its prefix
 ///
 /// The message to sign must contain the record public key & staleness ID
 // +------------------------+------------------+------------------+------------------+------------------+------------------+------------------+
@@ -57,27 +88,307 @@ pub const STALENESS_SUFFIX: &[u8; 15] = b"\nStaleness ID: ";
 // |                        | staleness_id +   |                  |                  |                  |                  |                  |
 // |                        | STALENESS_SUFFIX)|                  |                  |                  |                  |                  |
 // +------------------------+------------------+------------------+------------------+------------------+------------------+------------------+
-fn message_to_sign(content: &[u8], record_key: &Pubkey, staleness_id: &Pubkey) -> Vec<u8> {
-    let mut buffer = Vec::new();
-    let record_key_base58 = record_key.to_string();
-    let staleness_id_base58 = staleness_id.to_string();
-    let hex_encoded_content = hex::encode(content);
+pub fn message_to_sign(content: &[u8], record_key: &Pubkey, staleness_id: &Pubkey) -> Vec<u8> {
+    personal_message(
+        content,
+        record_key,
+        STALENESS_SUFFIX,
+        staleness_id.to_string().as_bytes(),
+    )
+}
+
+/// The EIP-191 message signed when the staleness is verified with `Validation::XChain`
+///
+/// The Solana staleness line is replaced by `XCHAIN_STALENESS_SUFFIX` followed by the Wormhole
+/// chain ID in decimal and the hex encoded 32 bytes address of the owner, e.g.
+/// `"\nXChain staleness ID: 2:0x000000000000000000000000<20 bytes address>"`
+pub fn xchain_message_to_sign(
+    content: &[u8],
+    record_key: &Pubkey,
+    chain_id: u16,
+    owner_address: &[u8; 32],
+) -> Vec<u8> {
+    personal_message(
+        content,
+        record_key,
+        XCHAIN_STALENESS_SUFFIX,
+        format!("{}:0x{}", chain_id, hex::encode(owner_address)).as_bytes(),
+    )
+}
+
+fn personal_message(
+    content: &[u8],
+    record_key: &Pubkey,
+    staleness_suffix: &[u8],
+    staleness_id: &[u8],
+) -> Vec<u8> {
+    let mut body = Vec::new();
+
+    body.extend_from_slice(hex::encode(content).as_bytes());
+    body.extend_from_slice(RECORD_SUFFIX);
+    body.extend_from_slice(record_key.to_string().as_bytes());
+    body.extend_from_slice(staleness_suffix);
+    body.extend_from_slice(staleness_id);
+
+    personal_sign_message(&body)
+}
+
+/// Prefixes `body` with `ETH_PREFIX_BYTES` and its length in decimal, as done by `personal_sign`
+pub fn personal_sign_message(body: &[u8]) -> Vec<u8> {
+    [
+        &ETH_PREFIX_BYTES[..],
+        body.len().to_string().as_bytes(),
+        body,
+    ]
+    .concat()
+}
+
+pub const EIP712_DOMAIN_TYPE: &[u8; 53] = b"EIP712Domain(string name,string version,bytes32 salt)";
+pub const RECORD_VALIDATION_TYPE: &[u8; 64] =
+    b"RecordValidation(bytes32 record,bytes32 staleness,bytes content)";
+pub const XCHAIN_RECORD_VALIDATION_TYPE: &[u8; 79] =
+    b"XChainRecordValidation(bytes32 record,uint16 chain,bytes32 owner,bytes content)";
+pub const EIP712_VERSION: &str = "1";
+
+#[cfg(not(feature = "devnet"))]
+pub const EIP712_NAME: &str = "SNS Records mainnet-beta";
+
+#[cfg(feature = "devnet")]
+pub const EIP712_NAME: &str = "SNS Records devnet";
+
+/// The EIP-712 domain separator, the salt is the program ID
+// EIP712Domain {
+//   name: EIP712_NAME,
+//   version: EIP712_VERSION,
+//   salt: crate::ID,
+// }
+pub fn eip712_domain_separator() -> [u8; 32] {
+    hashv(&[
+        hashv(&[EIP712_DOMAIN_TYPE]).as_ref(),
+        hashv(&[EIP712_NAME.as_bytes()]).as_ref(),
+        hashv(&[EIP712_VERSION.as_bytes()]).as_ref(),
+        crate::ID.as_ref(),
+    ])
+    .to_bytes()
+}
+
+/// The EIP-712 encoding of the typed data to sign, its Keccak-256 hash is signed
+// RecordValidation {
+//   record: record_key,
+//   staleness: staleness_id,
+//   content: content,
+// }
+pub fn eip712_message(content: &[u8], record_key: &Pubkey, staleness_id: &Pubkey) -> Vec<u8> {
+    let struct_hash = hashv(&[
+        hashv(&[RECORD_VALIDATION_TYPE]).as_ref(),
+        record_key.as_ref(),
+        staleness_id.as_ref(),
+        hashv(&[content]).as_ref(),
+    ]);
+    eip712_typed_data(struct_hash.as_ref())
+}
+
+/// The EIP-712 encoding of the typed data to sign when the staleness is verified with
+/// `Validation::XChain`
+// XChainRecordValidation {
+//   record: record_key,
+//   chain: chain_id,
+//   owner: owner_address,
+//   content: content,
+// }
+pub fn xchain_eip712_message(
+    content: &[u8],
+    record_key: &Pubkey,
+    chain_id: u16,
+    owner_address: &[u8; 32],
+) -> Vec<u8> {
+    // ABI encoding of the `uint16`: left padded big endian
+    let mut encoded_chain_id = [0; 32];
+    encoded_chain_id[30..].copy_from_slice(&chain_id.to_be_bytes());
+    let struct_hash = hashv(&[
+        hashv(&[XCHAIN_RECORD_VALIDATION_TYPE]).as_ref(),
+        record_key.as_ref(),
+        &encoded_chain_id,
+        owner_address,
+        hashv(&[content]).as_ref(),
+    ]);
+    eip712_typed_data(struct_hash.as_ref())
+}
+
+fn eip712_typed_data(struct_hash: &[u8]) -> Vec<u8> {
+    [b"\x19\x01", &eip712_domain_separator()[..], struct_hash].concat()
+}
+
+/// The EIP-712 hash of the typed data to sign
+pub fn eip712_message_hash(content: &[u8], record_key: &Pubkey, staleness_id: &Pubkey) -> [u8; 32] {
+    hashv(&[&eip712_message(content, record_key, staleness_id)]).to_bytes()
+}
+
+/// The message signed for the given format
+pub fn message_for_format(
+    format: EthereumMessageFormat,
+    content: &[u8],
+    record_key: &Pubkey,
+    staleness_id: &Pubkey,
+) -> Vec<u8> {
+    match format {
+        EthereumMessageFormat::Eip191 => message_to_sign(content, record_key, staleness_id),
+        EthereumMessageFormat::Eip712 => eip712_message(content, record_key, staleness_id),
+    }
+}
+
+/// The XChain message signed for the given format
+pub fn xchain_message_for_format(
+    format: EthereumMessageFormat,
+    content: &[u8],
+    record_key: &Pubkey,
+    chain_id: u16,
+    owner_address: &[u8; 32],
+) -> Vec<u8> {
+    match format {
+        EthereumMessageFormat::Eip191 => {
+            xchain_message_to_sign(content, record_key, chain_id, owner_address)
+        }
+        EthereumMessageFormat::Eip712 => {
+            xchain_eip712_message(content, record_key, chain_id, owner_address)
+        }
+    }
+}
 
-    let content_length = hex_encoded_content.len()
-        + record_key_base58.len()
-        + staleness_id_base58.len()
-        + RECORD_SUFFIX.len()
-        + STALENESS_SUFFIX.len();
+/// The message signed for a record in the given format, depending on how its staleness was
+/// verified
+pub fn record_message(
+    format: EthereumMessageFormat,
+    record: &SignedRecord,
+) -> Result<Vec<u8>, ProgramError> {
+    if record.staleness_validation == Validation::XChain {
+        let (chain_id, owner_address) = record.xchain_staleness_id()?;
+        return Ok(xchain_message_for_format(
+            format,
+            record.content,
+            record.record_key,
+            chain_id,
+            &owner_address,
+        ));
+    }
+    Ok(message_for_format(
+        format,
+        record.content,
+        record.record_key,
+        &record.staleness_key()?,
+    ))
+}
 
-    buffer.extend_from_slice(ETH_PREFIX_BYTES);
-    buffer.extend_from_slice(content_length.to_string().as_bytes());
-    buffer.extend_from_slice(hex_encoded_content.as_bytes());
-    buffer.extend_from_slice(RECORD_SUFFIX);
-    buffer.extend_from_slice(record_key_base58.as_bytes());
-    buffer.extend_from_slice(STALENESS_SUFFIX);
-    buffer.extend_from_slice(staleness_id_base58.as_bytes());
+/// The Ethereum address of a secp256k1 public key
+#[cfg(feature = "client")]
+pub fn eth_address(public_key: &libsecp256k1::PublicKey) -> [u8; 20] {
+    let mut address = [0; 20];
+    address.copy_from_slice(&hashv(&[&public_key.serialize()[1..]]).as_ref()[12..]);
+    address
+}
+
+/// Signs the Keccak-256 hash of `message`, returns the `signature` expected by `Params`: `r`, `s`
+/// and `v` in {27, 28}
+#[cfg(feature = "client")]
+pub fn sign_message(secret_key: &libsecp256k1::SecretKey, message: &[u8]) -> [u8; 65] {
+    let hash = hashv(&[message]).to_bytes();
+    let (signature, recovery_id) =
+        libsecp256k1::sign(&libsecp256k1::Message::parse(&hash), secret_key);
+    let mut result = [0; 65];
+    result[..64].copy_from_slice(&signature.serialize());
+    result[64] = 27 + recovery_id.serialize();
+    result
+}
 
-    buffer
+/// Signs the message of a record in the given format
+#[cfg(feature = "client")]
+pub fn sign_record(
+    secret_key: &libsecp256k1::SecretKey,
+    format: EthereumMessageFormat,
+    content: &[u8],
+    record_key: &Pubkey,
+    staleness_id: &Pubkey,
+) -> [u8; 65] {
+    sign_message(
+        secret_key,
+        &message_for_format(format, content, record_key, staleness_id),
+    )
+}
+
+/// Half the order of the secp256k1 curve, the maximum `s` value of a non-malleable signature
+const SECP256K1_HALF_ORDER: [u8; 32] = [
+    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
+/// Parses a signature into `r || s` and its recovery ID
+///
+/// The signature is either 65 bytes long, `r`, `s` and `v` in {27, 28} or {0, 1}, or a 64 bytes
+/// EIP-2098 compact signature, `r` followed by `s` with the y parity in its highest bit. Signatures
+/// with a high `s` value are rejected as they are malleable.
+pub fn normalize_signature(signature: &[u8]) -> Result<([u8; 64], u8), SnsRecordsError> {
+    let mut normalized = [0; 64];
+    let recovery_id = match signature.len() {
+        65 => {
+            normalized.copy_from_slice(&signature[..64]);
+            match signature[64] {
+                v @ (0 | 1) => v,
+                v @ (27 | 28) => v - 27,
+                _ => return Err(SnsRecordsError::InvalidRecoveryId),
+            }
+        }
+        64 => {
+            normalized.copy_from_slice(signature);
+            let y_parity = normalized[32] >> 7;
+            normalized[32] &= 0x7f;
+            y_parity
+        }
+        _ => return Err(SnsRecordsError::InvalidSignatureLength),
+    };
+
+    if normalized[32..] > SECP256K1_HALF_ORDER[..] {
+        return Err(SnsRecordsError::HighSSignature);
+    }
+
+    Ok((normalized, recovery_id))
+}
+
+/// Verifies EIP-191 signatures
+pub struct EthereumVerifier;
+
+impl SignatureVerifier for EthereumVerifier {
+    fn id_len(&self) -> usize {
+        get_validation_length(Validation::Ethereum) as usize
+    }
+
+    fn build_message(&self, record: &SignedRecord) -> Result<Vec<u8>, ProgramError> {
+        record_message(EthereumMessageFormat::Eip191, record)
+    }
+
+    fn verify(&self, message: &[u8], signature: &[u8], id: &[u8]) -> ProgramResult {
+        let (signature, recovery_id) = normalize_signature(signature)?;
+
+        let mut hasher = Hasher::default();
+        hasher.hash(message);
+        let hash = hasher.result();
+
+        let recovered_pubkey = secp256k1_recover(hash.as_ref(), recovery_id, &signature)
+            .map_err(|_| SnsRecordsError::Secp256k1Recover)?;
+
+        // Hash the public key using Keccak-256
+        let mut hasher = Hasher::default();
+        hasher.hash(&recovered_pubkey.0);
+        let output = hasher.result();
+
+        // Take the last 20 bytes of the hash to get the Ethereum address
+        let eth_address = output.0.get(12..).ok_or(SnsRecordsError::OutOfBound)?;
+
+        if eth_address != id {
+            return Err(SnsRecordsError::EthPubkeyMismatch.into());
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(InstructionsAccount)]
@@ -138,94 +449,50 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
     }
 }
 
-pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: ParamsV2) -> ProgramResult {
     let accounts = Accounts::parse(accounts)?;
-    let Params {
+    let ParamsV2 {
         validation: _,
         signature,
         expected_pubkey,
+        format,
     } = params;
 
     check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
     check_domain_parent(accounts.record, accounts.domain.key)?;
+    check_not_pending_upload(accounts.record)?;
 
-    let (new_buffer, current_length, header) = {
+    {
         let record_data = accounts.record.data.borrow();
-        let mut header = RecordHeader::from_buffer(&record_data);
-        let (_, buffer) = record_data.split_at(NameRecordHeader::LEN + RecordHeader::LEN);
-
-        let (staleness_id, rest) = buffer
-            .split_at(get_validation_length(header.staleness_validation.try_into()?) as usize);
-        let (_, content) = rest.split_at(get_validation_length(
-            header.right_of_association_validation.try_into()?,
-        ) as usize);
-
-        // Implicitly means that if the staleness is not verified it's
-        // impossible to verify the RoA
-        let staleness_id_array: [u8; 32] = staleness_id
-            .try_into()
-            .map_err(|_| SnsRecordsError::OutOfBound)?;
-        let buffer = message_to_sign(
-            content,
-            accounts.record.key,
-            &Pubkey::from(staleness_id_array),
-        );
-
-        let recovery_id = signature[64]
-            .checked_sub(27)
-            .ok_or(SnsRecordsError::NumericalOverflow)?;
-
-        let mut hasher = Hasher::default();
-        hasher.hash(&buffer);
-        let hash = hasher.result();
+        let record = SignedRecord::from_record_data(accounts.record.key, &record_data)?;
 
-        let recovered_pubkey = secp256k1_recover(
-            hash.as_ref(),
-            recovery_id,
-            signature.get(0..64).ok_or(SnsRecordsError::OutOfBound)?,
-        )
-        .map_err(|_| SnsRecordsError::Secp256k1Recover)?;
+        let message = record_message(format, &record)?;
 
-        // Hash the public key using Keccak-256
-        let mut hasher = Hasher::default();
-        hasher.hash(&recovered_pubkey.0);
-        let output = hasher.result();
-
-        // Take the last 20 bytes of the hash to get the Ethereum address
-        let eth_address = output.0.get(12..).ok_or(SnsRecordsError::OutOfBound)?;
-
-        if eth_address != expected_pubkey {
-            return Err(SnsRecordsError::EthPubkeyMismatch.into());
-        }
-
-        /////////
-
-        header.right_of_association_validation = Validation::Ethereum as u16;
-
-        let mut new_buffer: Vec<u8> = vec![];
-        new_buffer.extend_from_slice(staleness_id);
-        new_buffer.extend_from_slice(&expected_pubkey);
-        new_buffer.extend_from_slice(content);
-
-        (new_buffer, buffer.len(), header)
-    };
-
-    if new_buffer.len() != current_length {
-        cpi::resize_record(
-            accounts.record,
-            accounts.central_state,
-            accounts.fee_payer,
-            accounts.system_program,
-            (new_buffer.len() + RecordHeader::LEN)
-                .try_into()
-                .map_err(|_| SnsRecordsError::NumericalOverflow)?,
+        EthereumVerifier.verify(&message, &signature, &expected_pubkey)?;
+        check_validated_address_roa(
+            accounts.domain.key,
+            accounts.record.key,
+            Validation::Ethereum,
+            &expected_pubkey,
+            record.content,
         )?;
     }
 
-    let header_bytes = bytemuck::bytes_of(&header);
-    let data = [header_bytes, &new_buffer].concat();
-
-    cpi::edit_record(&data, 0, accounts.record, accounts.central_state)?;
+    update_roa(
+        accounts.record,
+        accounts.central_state,
+        accounts.fee_payer,
+        accounts.system_program,
+        ValidationEvent::ValidateRoa(Validation::Ethereum),
+        &expected_pubkey,
+    )?;
+
+    events::emit(
+        RecordEventKind::RoaVerified,
+        accounts.record,
+        accounts.domain.key,
+        None,
+    )?;
 
     Ok(())
 }
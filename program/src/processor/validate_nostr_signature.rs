@@ -0,0 +1,186 @@
+//! Validate a Nostr signature
+
+use crate::{
+    error::SnsRecordsError,
+    events::{self, RecordEventKind},
+    processor::validate_ethereum_signature::{RECORD_SUFFIX, STALENESS_SUFFIX},
+    schnorr,
+    state::validation::{get_validation_length, Validation, ValidationEvent},
+    utils::{check_domain_owner, check_domain_parent, check_not_pending_upload, update_roa},
+    verifier::{SignatureVerifier, SignedRecord},
+};
+
+use {
+    bonfida_utils::checks::check_account_owner,
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::hash::hash,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_program,
+    },
+    std::convert::TryInto,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    /// The BIP-340 signature of the SHA-256 hash of the message
+    pub signature: [u8; 64],
+    /// The expected Nostr x-only public key
+    pub expected_pubkey: [u8; 32],
+}
+
+pub const NOSTR_PREFIX_BYTES: &[u8; 22] = b"Nostr Signed Message:\n";
+
+///
+/// The message to sign must contain the record public key & staleness ID
+/// Its SHA-256 hash is signed
+// +------------------------+------------------+------------------+------------------+------------------+------------------+
+// | NOSTR_PREFIX_BYTES     | content          | RECORD_SUFFIX    | record_key       | STALENESS_SUFFIX | staleness_id     |
+// +------------------------+------------------+------------------+------------------+------------------+------------------+
+// | "Nostr Signed          | Hex encoded      | "\nFor record: " | Public key of    | "\nStaleness ID: " | Public key of   |
+// | Message:\n"            | content          |                  | the record       |                  | the staleness    |
+// +------------------------+------------------+------------------+------------------+------------------+------------------+
+pub fn message_to_sign(content: &[u8], record_key: &Pubkey, staleness_id: &Pubkey) -> Vec<u8> {
+    let mut buffer = Vec::new();
+
+    buffer.extend_from_slice(NOSTR_PREFIX_BYTES);
+    buffer.extend_from_slice(hex::encode(content).as_bytes());
+    buffer.extend_from_slice(RECORD_SUFFIX);
+    buffer.extend_from_slice(record_key.to_string().as_bytes());
+    buffer.extend_from_slice(STALENESS_SUFFIX);
+    buffer.extend_from_slice(staleness_id.to_string().as_bytes());
+
+    buffer
+}
+
+/// Verifies BIP-340 signatures of the SHA-256 hash of the message
+pub struct NostrVerifier;
+
+impl SignatureVerifier for NostrVerifier {
+    fn id_len(&self) -> usize {
+        get_validation_length(Validation::Nostr) as usize
+    }
+
+    fn build_message(&self, record: &SignedRecord) -> Result<Vec<u8>, ProgramError> {
+        Ok(message_to_sign(
+            record.content,
+            record.record_key,
+            &record.staleness_key()?,
+        ))
+    }
+
+    fn verify(&self, message: &[u8], signature: &[u8], id: &[u8]) -> ProgramResult {
+        let signature: &[u8; 64] = signature
+            .try_into()
+            .map_err(|_| SnsRecordsError::OutOfBound)?;
+        let pubkey: &[u8; 32] = id.try_into().map_err(|_| SnsRecordsError::OutOfBound)?;
+
+        if !schnorr::verify(pubkey, hash(message).as_ref(), signature) {
+            return Err(SnsRecordsError::InvalidSignature.into());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The SPL token program account
+    pub spl_name_service_program: &'a T,
+
+    #[cons(writable, signer)]
+    /// The fee payer account
+    pub fee_payer: &'a T,
+
+    #[cons(writable)]
+    /// The record account to validate
+    pub record: &'a T,
+
+    #[cons(writable)]
+    /// The domain name owning the record
+    pub domain: &'a T,
+
+    #[cons(writable, signer)]
+    /// The domain owner
+    pub domain_owner: &'a T,
+
+    /// The SNS Record central state
+    pub central_state: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            spl_name_service_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            record: next_account_info(accounts_iter)?,
+            domain: next_account_info(accounts_iter)?,
+            domain_owner: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.spl_name_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.central_state, &crate::central_state::KEY)?;
+
+        // Check owners
+        check_account_owner(accounts.record, &spl_name_service::ID)?;
+        check_account_owner(accounts.domain, &spl_name_service::ID)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+        check_signer(accounts.domain_owner)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+    let Params {
+        signature,
+        expected_pubkey,
+    } = params;
+
+    check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
+    check_domain_parent(accounts.record, accounts.domain.key)?;
+    check_not_pending_upload(accounts.record)?;
+
+    {
+        let record_data = accounts.record.data.borrow();
+        let record = SignedRecord::from_record_data(accounts.record.key, &record_data)?;
+        let message = NostrVerifier.build_message(&record)?;
+        NostrVerifier.verify(&message, &signature, &expected_pubkey)?;
+    }
+
+    update_roa(
+        accounts.record,
+        accounts.central_state,
+        accounts.fee_payer,
+        accounts.system_program,
+        ValidationEvent::ValidateRoa(Validation::Nostr),
+        &expected_pubkey,
+    )?;
+
+    events::emit(
+        RecordEventKind::RoaVerified,
+        accounts.record,
+        accounts.domain.key,
+        None,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,224 @@
+//! Validate a passkey (WebAuthn) signature
+
+use crate::{
+    error::SnsRecordsError,
+    events::{self, RecordEventKind},
+    precompile::{self, SECP256R1_PROGRAM_ID},
+    processor::validate_ed25519_signature::message_to_sign,
+    state::validation::{Validation, ValidationEvent},
+    utils::{check_domain_owner, check_domain_parent, check_not_pending_upload, update_roa},
+    verifier::SignedRecord,
+};
+
+use {
+    base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine},
+    bonfida_utils::checks::check_account_owner,
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::hash::hash,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        instruction::Instruction as SolanaInstruction,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_program,
+        sysvar::instructions::{self, get_instruction_relative},
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    /// The WebAuthn authenticator data
+    pub authenticator_data: Vec<u8>,
+    /// The WebAuthn client data JSON
+    pub client_data_json: Vec<u8>,
+}
+
+/// Compressed P-256 public key
+pub const PUBKEY_LEN: usize = 33;
+
+/// Serialization of the client data of an assertion up to the challenge, as defined by the
+/// WebAuthn specification
+pub const CLIENT_DATA_PREFIX: &[u8; 36] = br#"{"type":"webauthn.get","challenge":""#;
+
+/// User present flag of the authenticator data
+const FLAG_USER_PRESENT: u8 = 0x01;
+/// Offset of the flags in the authenticator data, after the RP ID hash
+const FLAGS_OFFSET: usize = 32;
+/// RP ID hash, flags and signature counter
+const AUTHENTICATOR_DATA_MIN_LEN: usize = 37;
+
+/// The WebAuthn challenge: SHA-256 of the message signed by Ed25519 verifiers
+pub fn challenge(
+    record_key: &Pubkey,
+    staleness_validation: Validation,
+    staleness_id: &[u8],
+    content: &[u8],
+) -> [u8; 32] {
+    hash(&message_to_sign(
+        record_key,
+        staleness_validation,
+        staleness_id,
+        content,
+    ))
+    .to_bytes()
+}
+
+/// The message signed by the passkey, the authenticator data followed by the SHA-256 hash of the
+/// client data JSON
+pub fn signed_message(authenticator_data: &[u8], client_data_json: &[u8]) -> Vec<u8> {
+    [authenticator_data, hash(client_data_json).as_ref()].concat()
+}
+
+/// Builds the secp256r1 precompile instruction that must directly precede the validation
+pub fn new_secp256r1_instruction(
+    pubkey: &[u8; PUBKEY_LEN],
+    signature: &[u8; precompile::SIGNATURE_LEN],
+    authenticator_data: &[u8],
+    client_data_json: &[u8],
+) -> SolanaInstruction {
+    precompile::new_instruction(
+        &SECP256R1_PROGRAM_ID,
+        pubkey,
+        signature,
+        &signed_message(authenticator_data, client_data_json),
+    )
+}
+
+fn check_client_data(client_data_json: &[u8], challenge: &[u8; 32]) -> Result<(), ProgramError> {
+    let encoded_challenge = URL_SAFE_NO_PAD.encode(challenge);
+    let expected_prefix = [CLIENT_DATA_PREFIX, encoded_challenge.as_bytes(), b"\""].concat();
+    if !client_data_json.starts_with(&expected_prefix) {
+        return Err(SnsRecordsError::InvalidWebAuthnData.into());
+    }
+    Ok(())
+}
+
+fn check_authenticator_data(authenticator_data: &[u8]) -> Result<(), ProgramError> {
+    if authenticator_data.len() < AUTHENTICATOR_DATA_MIN_LEN
+        || authenticator_data[FLAGS_OFFSET] & FLAG_USER_PRESENT == 0
+    {
+        return Err(SnsRecordsError::InvalidWebAuthnData.into());
+    }
+    Ok(())
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The SPL token program account
+    pub spl_name_service_program: &'a T,
+
+    #[cons(writable, signer)]
+    /// The fee payer account
+    pub fee_payer: &'a T,
+
+    #[cons(writable)]
+    /// The record account to validate
+    pub record: &'a T,
+
+    #[cons(writable)]
+    /// The domain name owning the record
+    pub domain: &'a T,
+
+    #[cons(writable, signer)]
+    /// The domain owner
+    pub domain_owner: &'a T,
+
+    /// The SNS Record central state
+    pub central_state: &'a T,
+
+    /// The instructions sysvar account
+    pub instructions_sysvar: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            spl_name_service_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            record: next_account_info(accounts_iter)?,
+            domain: next_account_info(accounts_iter)?,
+            domain_owner: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            instructions_sysvar: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.spl_name_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.central_state, &crate::central_state::KEY)?;
+        check_account_key(accounts.instructions_sysvar, &instructions::ID)?;
+
+        // Check owners
+        check_account_owner(accounts.record, &spl_name_service::ID)?;
+        check_account_owner(accounts.domain, &spl_name_service::ID)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+        check_signer(accounts.domain_owner)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+    let Params {
+        authenticator_data,
+        client_data_json,
+    } = params;
+
+    check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
+    check_domain_parent(accounts.record, accounts.domain.key)?;
+    check_not_pending_upload(accounts.record)?;
+
+    let secp256r1_instruction = get_instruction_relative(-1, accounts.instructions_sysvar)?;
+    let (pubkey, message) =
+        precompile::parse_instruction(&secp256r1_instruction, &SECP256R1_PROGRAM_ID, PUBKEY_LEN)?;
+
+    if message != signed_message(&authenticator_data, &client_data_json) {
+        return Err(SnsRecordsError::SignedMessageMismatch.into());
+    }
+    check_authenticator_data(&authenticator_data)?;
+
+    {
+        let record_data = accounts.record.data.borrow();
+        let record = SignedRecord::from_record_data(accounts.record.key, &record_data)?;
+        check_client_data(
+            &client_data_json,
+            &challenge(
+                record.record_key,
+                record.staleness_validation,
+                record.staleness_id,
+                record.content,
+            ),
+        )?;
+    }
+
+    update_roa(
+        accounts.record,
+        accounts.central_state,
+        accounts.fee_payer,
+        accounts.system_program,
+        ValidationEvent::ValidateRoa(Validation::Passkey),
+        pubkey,
+    )?;
+
+    events::emit(
+        RecordEventKind::RoaVerified,
+        accounts.record,
+        accounts.domain.key,
+        None,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,147 @@
+//! Validate a RoA with the signature verifier of the validation
+//!
+//! A partial replacement of the per-chain validate instructions, which remain the canonical
+//! paths, see `crate::verifier` for the supported validations
+
+use crate::{
+    address::check_validated_address_roa,
+    error::SnsRecordsError,
+    events::{self, RecordEventKind},
+    state::validation::{Validation, ValidationEvent},
+    utils::{check_domain_owner, check_domain_parent, check_not_pending_upload, update_roa},
+    verifier::{get_verifier, SignedRecord},
+};
+
+use {
+    bonfida_utils::checks::check_account_owner,
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    /// The validation of the RoA
+    pub validation: Validation,
+    /// The signature of the message built by the verifier
+    pub signature: Vec<u8>,
+    /// The RoA ID to write, must match the length of the validation
+    pub roa_id: Vec<u8>,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The SPL token program account
+    pub spl_name_service_program: &'a T,
+
+    #[cons(writable, signer)]
+    /// The fee payer account
+    pub fee_payer: &'a T,
+
+    #[cons(writable)]
+    /// The record account to validate
+    pub record: &'a T,
+
+    #[cons(writable)]
+    /// The domain name owning the record
+    pub domain: &'a T,
+
+    #[cons(writable, signer)]
+    /// The domain owner
+    pub domain_owner: &'a T,
+
+    /// The SNS Record central state
+    pub central_state: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            spl_name_service_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            record: next_account_info(accounts_iter)?,
+            domain: next_account_info(accounts_iter)?,
+            domain_owner: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.spl_name_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.central_state, &crate::central_state::KEY)?;
+
+        // Check owners
+        check_account_owner(accounts.record, &spl_name_service::ID)?;
+        check_account_owner(accounts.domain, &spl_name_service::ID)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+        check_signer(accounts.domain_owner)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+    let Params {
+        validation,
+        signature,
+        roa_id,
+    } = params;
+
+    check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
+    check_domain_parent(accounts.record, accounts.domain.key)?;
+    check_not_pending_upload(accounts.record)?;
+
+    let verifier = get_verifier(validation)?;
+    if roa_id.len() != verifier.id_len() {
+        return Err(SnsRecordsError::InvalidRoaLength.into());
+    }
+
+    {
+        let record_data = accounts.record.data.borrow();
+        let record = SignedRecord::from_record_data(accounts.record.key, &record_data)?;
+        let message = verifier.build_message(&record)?;
+        verifier.verify(&message, &signature, &roa_id)?;
+        check_validated_address_roa(
+            accounts.domain.key,
+            accounts.record.key,
+            validation,
+            &roa_id,
+            record.content,
+        )?;
+    }
+
+    update_roa(
+        accounts.record,
+        accounts.central_state,
+        accounts.fee_payer,
+        accounts.system_program,
+        ValidationEvent::ValidateRoa(validation),
+        &roa_id,
+    )?;
+
+    events::emit(
+        RecordEventKind::RoaVerified,
+        accounts.record,
+        accounts.domain.key,
+        None,
+    )?;
+
+    Ok(())
+}
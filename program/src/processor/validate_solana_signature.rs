@@ -1,16 +1,14 @@
 //! Validate a RoA or Staleness via Solana signature
 
-use std::convert::TryInto;
-
-use spl_name_service::state::NameRecordHeader;
-
 use crate::{
+    address::check_validated_address_roa,
     error::SnsRecordsError,
+    events::{self, RecordEventKind},
     state::{
-        record_header::RecordHeader,
-        validation::{get_validation_length, Validation},
+        record_view::RecordView,
+        validation::{Validation, ValidationEvent},
     },
-    utils::{check_domain_owner, check_domain_parent},
+    utils::{check_domain_owner, check_domain_parent, check_not_pending_upload},
 };
 
 use {
@@ -24,7 +22,6 @@ use {
         account_info::{next_account_info, AccountInfo},
         entrypoint::ProgramResult,
         program_error::ProgramError,
-        program_pack::Pack,
         pubkey::Pubkey,
         system_program,
     },
@@ -99,12 +96,18 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
 
 pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
     let accounts = Accounts::parse(accounts)?;
+    check_not_pending_upload(accounts.record)?;
     let Params { staleness } = params;
+    let kind = if staleness {
+        RecordEventKind::StalenessValidated
+    } else {
+        RecordEventKind::RoaVerified
+    };
 
     let (new_buffer, current_length, header) = {
         let record_data = accounts.record.data.borrow();
-        let mut header = RecordHeader::from_buffer(&record_data);
-        let (_, buffer) = record_data.split_at(NameRecordHeader::LEN + RecordHeader::LEN);
+        let record = RecordView::new(&record_data)?;
+        let mut header = record.header();
 
         let mut new_buffer: Vec<u8> = vec![];
 
@@ -112,41 +115,61 @@ pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -
             check_signer(accounts.domain_owner)?;
             check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
             check_domain_parent(accounts.record, accounts.domain.key)?;
-            let (_, rest) = buffer
-                .split_at(get_validation_length(header.staleness_validation.try_into()?) as usize);
 
             if accounts.domain_owner.owner == &sns_warp_common::constants::EMITTER_KEY {
                 // This is a xchain_owned account
-                header.staleness_validation = Validation::XChain as u16;
+                header.set_validation_state(
+                    record
+                        .validation_state()
+                        .transition(ValidationEvent::ValidateStaleness(Validation::XChain))?,
+                );
                 let record = sns_warp_common::state::x_domain::XDomain::try_from_slice(
                     &accounts.domain_owner.data.borrow(),
                 )?;
                 new_buffer.extend_from_slice(&record.owner_chain.to_le_bytes());
                 new_buffer.extend_from_slice(&record.owner_address);
             } else {
-                header.staleness_validation = Validation::Solana as u16;
+                header.set_validation_state(
+                    record
+                        .validation_state()
+                        .transition(ValidationEvent::ValidateStaleness(Validation::Solana))?,
+                );
                 new_buffer.extend_from_slice(accounts.verifier.key.as_ref());
             }
 
-            header.right_of_association_validation = Validation::None as u16;
-            new_buffer.extend_from_slice(rest)
+            // The transition clears the RoA, its ID is dropped
+            new_buffer.extend_from_slice(record.content());
         } else {
-            let (_, rest) = buffer
-                .split_at(get_validation_length(header.staleness_validation.try_into()?) as usize);
-
-            let expected_verifier = rest.get(..32).ok_or(SnsRecordsError::OutOfBound)?;
-
-            if expected_verifier != accounts.verifier.key.as_ref() {
+            // The RoA ID is declared, see `ValidationEvent::ValidateRoa`
+            header.set_validation_state(
+                record
+                    .validation_state()
+                    .transition(ValidationEvent::ValidateRoa(Validation::Solana))?,
+            );
+
+            if record.roa_id() != accounts.verifier.key.as_ref() {
                 return Err(SnsRecordsError::InvalidVerifier.into());
             }
 
-            header.right_of_association_validation = Validation::Solana as u16;
+            check_domain_parent(accounts.record, accounts.domain.key)?;
+            check_validated_address_roa(
+                accounts.domain.key,
+                accounts.record.key,
+                Validation::Solana,
+                record.roa_id(),
+                record.content(),
+            )?;
 
             // The buffer remains unchanged
-            new_buffer = buffer.to_vec();
+            new_buffer.extend_from_slice(record.staleness_id());
+            new_buffer.extend_from_slice(record.after_staleness_id());
         }
 
-        (new_buffer, buffer.len(), header)
+        (
+            new_buffer,
+            record.data_len(),
+            record.versioned_header().with_header(header),
+        )
     };
 
     if new_buffer.len() != current_length {
@@ -155,14 +178,15 @@ pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -
             accounts.central_state,
             accounts.fee_payer,
             accounts.system_program,
-            (new_buffer.len() + RecordHeader::LEN) as u32,
+            (new_buffer.len() + header.size()) as u32,
         )?;
     }
 
-    let header_bytes = bytemuck::bytes_of(&header);
-    let data = [header_bytes, &new_buffer].concat();
+    let data = [header.bytes(), &new_buffer].concat();
 
     cpi::edit_record(&data, 0, accounts.record, accounts.central_state)?;
 
+    events::emit(kind, accounts.record, accounts.domain.key, None)?;
+
     Ok(())
 }
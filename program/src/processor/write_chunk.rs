@@ -0,0 +1,136 @@
+//! Write a chunk of content at a given offset of a record pending an upload
+
+use bonfida_utils::checks::check_account_owner;
+use solana_program::program_pack::Pack;
+use spl_name_service::state::NameRecordHeader;
+
+use crate::{
+    error::SnsRecordsError,
+    state::record_header::VersionedRecordHeader,
+    utils::{check_domain_owner, check_domain_parent},
+};
+
+use {
+    crate::cpi,
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_program,
+    },
+    std::convert::TryInto,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    /// The offset of the chunk in the record content
+    pub offset: u32,
+    pub data: Vec<u8>,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The SPL token program account
+    pub spl_name_service_program: &'a T,
+
+    #[cons(writable, signer)]
+    /// The fee payer account
+    pub fee_payer: &'a T,
+
+    #[cons(writable)]
+    /// The record account to write to
+    pub record: &'a T,
+
+    #[cons(writable)]
+    /// The domain name owning the record
+    pub domain: &'a T,
+
+    #[cons(writable, signer)]
+    /// The domain owner
+    pub domain_owner: &'a T,
+
+    /// The SNS Record central state
+    pub central_state: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            spl_name_service_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            record: next_account_info(accounts_iter)?,
+            domain: next_account_info(accounts_iter)?,
+            domain_owner: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.spl_name_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.central_state, &crate::central_state::KEY)?;
+
+        // Check owners
+        check_account_owner(accounts.record, &spl_name_service::ID)?;
+        check_account_owner(accounts.domain, &spl_name_service::ID)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+        check_signer(accounts.domain_owner)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+    let Params { offset, data } = params;
+
+    check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
+    check_domain_parent(accounts.record, accounts.domain.key)?;
+
+    let header = VersionedRecordHeader::try_from_buffer(&accounts.record.data.borrow())?;
+    if !header.is_pending_upload() {
+        return Err(SnsRecordsError::UploadNotPending.into());
+    }
+
+    // Offsets are relative to the content, i.e they do not include the record header. Pending
+    // records are reset by `BeginUpload` and carry no validation ID.
+    let data_offset = offset
+        .checked_add(header.size() as u32)
+        .ok_or(SnsRecordsError::NumericalOverflow)?;
+    let data_end: u32 = (data_offset as usize)
+        .checked_add(data.len())
+        .and_then(|end| end.try_into().ok())
+        .ok_or(SnsRecordsError::NumericalOverflow)?;
+
+    let record_len = accounts.record.data_len() - NameRecordHeader::LEN;
+    if data_end as usize > record_len {
+        // The runtime caps the growth of the account per instruction, the chunks are expected to
+        // be written close to the end of the record
+        if data_end as usize - record_len > MAX_PERMITTED_DATA_INCREASE {
+            return Err(SnsRecordsError::ReallocLimitExceeded.into());
+        }
+        cpi::resize_record(
+            accounts.record,
+            accounts.central_state,
+            accounts.fee_payer,
+            accounts.system_program,
+            data_end,
+        )?;
+    }
+
+    cpi::edit_record(&data, data_offset, accounts.record, accounts.central_state)?;
+
+    Ok(())
+}
@@ -1,19 +1,13 @@
 //! Write a RoA in the record
 
-use std::convert::TryInto;
-
-use spl_name_service::state::NameRecordHeader;
-
 use crate::{
-    state::{
-        record_header::RecordHeader,
-        validation::{get_validation_length, Validation},
-    },
-    utils::{check_domain_owner, check_domain_parent},
+    error::SnsRecordsError,
+    events::{self, RecordEventKind},
+    state::validation::{get_validation_length, Validation, ValidationEvent},
+    utils::{check_domain_owner, check_domain_parent, check_not_pending_upload, update_roa},
 };
 
 use {
-    crate::cpi,
     bonfida_utils::{
         checks::{check_account_key, check_account_owner, check_signer},
         BorshSize, InstructionsAccount,
@@ -23,7 +17,6 @@ use {
         account_info::{next_account_info, AccountInfo},
         entrypoint::ProgramResult,
         program_error::ProgramError,
-        program_pack::Pack,
         pubkey::Pubkey,
         system_program,
     },
@@ -31,9 +24,28 @@ use {
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 pub struct Params {
+    /// The RoA ID to write, a Solana public key
     pub roa_id: Vec<u8>,
 }
 
+/// The parameters of `WriteRoaV2`, `Params` declare a `Validation::UnverifiedSolana` RoA ID
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct ParamsV2 {
+    /// The unverified kind of the RoA ID, naming the validation which must later prove it
+    pub validation: Validation,
+    /// The RoA ID to write, must match the length of the validation
+    pub roa_id: Vec<u8>,
+}
+
+impl From<Params> for ParamsV2 {
+    fn from(params: Params) -> Self {
+        Self {
+            validation: Validation::UnverifiedSolana,
+            roa_id: params.roa_id,
+        }
+    }
+}
+
 #[derive(InstructionsAccount)]
 pub struct Accounts<'a, T> {
     /// The system program account
@@ -91,48 +103,33 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
     }
 }
 
-pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: ParamsV2) -> ProgramResult {
     let accounts = Accounts::parse(accounts)?;
-    let Params { roa_id } = params;
+    let ParamsV2 { validation, roa_id } = params;
 
     check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
     check_domain_parent(accounts.record, accounts.domain.key)?;
+    check_not_pending_upload(accounts.record)?;
 
-    let (new_buffer, current_length, header) = {
-        let record_data = accounts.record.data.borrow();
-        let mut header = RecordHeader::from_buffer(&record_data);
-        let (_, buffer) = record_data.split_at(NameRecordHeader::LEN + RecordHeader::LEN);
-
-        let mut new_buffer: Vec<u8> = vec![];
-
-        let (staleness_id, rest) = buffer
-            .split_at(get_validation_length(header.staleness_validation.try_into()?) as usize);
-        let (_, content) = rest.split_at(get_validation_length(
-            header.right_of_association_validation.try_into()?,
-        ) as usize);
-
-        header.right_of_association_validation = Validation::UnverifiedSolana as u16;
-        new_buffer.extend_from_slice(staleness_id);
-        new_buffer.extend_from_slice(&roa_id);
-        new_buffer.extend_from_slice(content);
-
-        (new_buffer, buffer.len(), header)
-    };
-
-    if new_buffer.len() != current_length {
-        cpi::resize_record(
-            accounts.record,
-            accounts.central_state,
-            accounts.fee_payer,
-            accounts.system_program,
-            (new_buffer.len() + RecordHeader::LEN) as u32,
-        )?;
+    if roa_id.len() != get_validation_length(validation) as usize {
+        return Err(SnsRecordsError::InvalidRoaLength.into());
     }
 
-    let header_bytes = bytemuck::bytes_of(&header);
-    let data = [header_bytes, &new_buffer].concat();
-
-    cpi::edit_record(&data, 0, accounts.record, accounts.central_state)?;
+    update_roa(
+        accounts.record,
+        accounts.central_state,
+        accounts.fee_payer,
+        accounts.system_program,
+        ValidationEvent::DeclareRoa(validation),
+        &roa_id,
+    )?;
+
+    events::emit(
+        RecordEventKind::RoaWritten,
+        accounts.record,
+        accounts.domain.key,
+        None,
+    )?;
 
     Ok(())
 }
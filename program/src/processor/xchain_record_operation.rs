@@ -0,0 +1,278 @@
+//! Edit or delete a record of a XChain domain with the authorization of its foreign owner
+
+use bonfida_utils::checks::check_account_owner;
+use sns_warp_common::{state::x_domain::XDomain, wormhole::ChainIds};
+
+use crate::{
+    cpi,
+    error::SnsRecordsError,
+    processor::{
+        delete_record, edit_record,
+        validate_ethereum_signature::{personal_sign_message, EthereumVerifier, RECORD_SUFFIX},
+    },
+    state::xchain_nonce::XChainNonce,
+    utils::{check_domain_owner, get_record_key_and_seeds},
+    verifier::SignatureVerifier,
+};
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_program,
+    },
+};
+
+/// The operations which can be authorized by a foreign owner
+///
+/// Records cannot be allocated: the name service requires the signature of the domain owner
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+pub enum XChainOperation {
+    Edit { record: String, content: Vec<u8> },
+    Delete { record: String },
+}
+
+impl XChainOperation {
+    pub fn record(&self) -> &str {
+        match self {
+            XChainOperation::Edit { record, .. } | XChainOperation::Delete { record } => record,
+        }
+    }
+}
+
+impl BorshSize for XChainOperation {
+    fn borsh_len(&self) -> usize {
+        1 + match self {
+            XChainOperation::Edit { record, content } => record.borsh_len() + content.borsh_len(),
+            XChainOperation::Delete { record } => record.borsh_len(),
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Params {
+    /// The operation authorized by the foreign owner
+    pub operation: XChainOperation,
+    /// The nonce of the domain, see `XChainNonce`
+    pub nonce: u64,
+    /// The EIP-191 signature of `message_to_sign` by the foreign owner, see `normalize_signature`
+    /// for the accepted encodings
+    pub signature: Vec<u8>,
+    /// Whether to check the content of an edit against the type of the record, see
+    /// `edit_record::ParamsV2`
+    ///
+    /// The flag is not signed by the foreign owner since it cannot change the written content
+    pub validate_content: bool,
+}
+
+// The operation has a variable length, which the derived implementation does not support
+impl BorshSize for Params {
+    fn borsh_len(&self) -> usize {
+        self.operation.borsh_len() + 8 + self.signature.borsh_len() + 1
+    }
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The SPL token program account
+    pub spl_name_service_program: &'a T,
+
+    #[cons(writable, signer)]
+    /// The fee payer account, any relayer of the authorization
+    pub fee_payer: &'a T,
+
+    #[cons(writable)]
+    /// The record account to edit or delete
+    pub record: &'a T,
+
+    #[cons(writable)]
+    /// The domain name owning the record
+    pub domain: &'a T,
+
+    #[cons(writable)]
+    /// The domain owner, a `XDomain` account of the SNS Warp emitter
+    pub domain_owner: &'a T,
+
+    /// The SNS Record central state
+    pub central_state: &'a T,
+
+    #[cons(writable)]
+    /// The `XChainNonce` account of the domain
+    pub nonce: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            spl_name_service_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            record: next_account_info(accounts_iter)?,
+            domain: next_account_info(accounts_iter)?,
+            domain_owner: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            nonce: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.spl_name_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.central_state, &crate::central_state::KEY)?;
+        check_account_key(
+            accounts.nonce,
+            &XChainNonce::find_key(accounts.domain.key).0,
+        )?;
+
+        // Check owners
+        check_account_owner(accounts.record, &spl_name_service::ID)?;
+        check_account_owner(accounts.domain, &spl_name_service::ID)?;
+        if accounts.domain_owner.owner != &sns_warp_common::constants::EMITTER_KEY {
+            return Err(SnsRecordsError::NotXChainDomain.into());
+        }
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+
+        Ok(accounts)
+    }
+}
+
+/// The Wormhole IDs of the EVM chains, whose owners sign EIP-191 messages
+pub const EVM_CHAIN_IDS: [u16; 2] = [ChainIds::BNB as u16, ChainIds::BASE as u16];
+
+pub const XCHAIN_EDIT_PREFIX: &[u8; 13] = b"Edit record: ";
+pub const XCHAIN_DELETE_PREFIX: &[u8; 13] = b"Delete record";
+pub const NONCE_SUFFIX: &[u8; 8] = b"\nNonce: ";
+
+/// The EIP-191 message signed by the foreign owner to authorize the operation
+///
+/// The edited content is hex encoded, the record key is base58 encoded and the nonce is in
+/// decimal:
+// Edit record: <content>
+// For record: <record_key>
+// Nonce: <nonce>
+pub fn message_to_sign(operation: &XChainOperation, record_key: &Pubkey, nonce: u64) -> Vec<u8> {
+    let mut body = Vec::new();
+    match operation {
+        XChainOperation::Edit { content, .. } => {
+            body.extend_from_slice(XCHAIN_EDIT_PREFIX);
+            body.extend_from_slice(hex::encode(content).as_bytes());
+        }
+        XChainOperation::Delete { .. } => body.extend_from_slice(XCHAIN_DELETE_PREFIX),
+    }
+    body.extend_from_slice(RECORD_SUFFIX);
+    body.extend_from_slice(record_key.to_string().as_bytes());
+    body.extend_from_slice(NONCE_SUFFIX);
+    body.extend_from_slice(nonce.to_string().as_bytes());
+
+    personal_sign_message(&body)
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+    let Params {
+        operation,
+        nonce,
+        signature,
+        validate_content,
+    } = params;
+
+    check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
+    let x_domain = XDomain::try_from_slice(&accounts.domain_owner.data.borrow())?;
+    if x_domain.name_account != *accounts.domain.key {
+        return Err(SnsRecordsError::WrongDomainOwner.into());
+    }
+
+    let (record_key, _) = get_record_key_and_seeds(accounts.domain.key, operation.record());
+    check_account_key(accounts.record, &record_key)?;
+
+    ////
+    // Verify the authorization
+    ////
+
+    // The foreign owner signs with an EVM key, whose addresses are left padded with zeros in the
+    // Wormhole format
+    if !EVM_CHAIN_IDS.contains(&x_domain.owner_chain) {
+        return Err(SnsRecordsError::UnsupportedValidation.into());
+    }
+    let (padding, address) = x_domain.owner_address.split_at(12);
+    if padding.iter().any(|b| *b != 0) {
+        return Err(SnsRecordsError::UnsupportedValidation.into());
+    }
+
+    let expected_nonce = if accounts.nonce.data_is_empty() {
+        XChainNonce::default()
+    } else {
+        check_account_owner(accounts.nonce, &crate::ID)?;
+        XChainNonce::try_from_slice(&accounts.nonce.data.borrow())?
+    };
+    if nonce != expected_nonce.nonce {
+        return Err(SnsRecordsError::InvalidNonce.into());
+    }
+
+    let message = message_to_sign(&operation, accounts.record.key, nonce);
+    EthereumVerifier.verify(&message, &signature, address)?;
+
+    ////
+    // Consume the nonce
+    ////
+    if accounts.nonce.data_is_empty() {
+        let (_, bump) = XChainNonce::find_key(accounts.domain.key);
+        cpi::create_program_account(
+            accounts.nonce,
+            accounts.fee_payer,
+            accounts.system_program,
+            XChainNonce::LEN,
+            &[XChainNonce::SEED, accounts.domain.key.as_ref(), &[bump]],
+        )?;
+    }
+    XChainNonce { nonce: nonce + 1 }.serialize(&mut &mut accounts.nonce.data.borrow_mut()[..])?;
+
+    ////
+    // Execute the operation
+    ////
+    match operation {
+        XChainOperation::Edit { record, content } => edit_record::edit(
+            &edit_record::Accounts {
+                system_program: accounts.system_program,
+                spl_name_service_program: accounts.spl_name_service_program,
+                fee_payer: accounts.fee_payer,
+                record: accounts.record,
+                domain: accounts.domain,
+                domain_owner: accounts.domain_owner,
+                central_state: accounts.central_state,
+            },
+            edit_record::ParamsV2 {
+                record,
+                content,
+                validate_content,
+            },
+        ),
+        // As for `DeleteRecord`, the rent of the record is refunded to the domain owner rather than
+        // to the relayer, which the signed message does not name
+        XChainOperation::Delete { record } => delete_record::delete(
+            &delete_record::Accounts {
+                system_program: accounts.system_program,
+                spl_name_service_program: accounts.spl_name_service_program,
+                fee_payer: accounts.fee_payer,
+                record: accounts.record,
+                domain: accounts.domain,
+                domain_owner: accounts.domain_owner,
+                central_state: accounts.central_state,
+            },
+            Some(&record),
+            accounts.domain_owner,
+        ),
+    }
+}
@@ -0,0 +1,270 @@
+//! The record names defined by SNS-IP-3
+//!
+//! Record accounts are derived from the name of the record, a misspelled name such as `SoL`
+//! creates a record which resolvers never look up. Custom names remain supported by the program.
+//!
+//! Content validation is opt-in: `AllocateAndPostRecord` and `EditRecord` keep accepting any
+//! content so that existing clients are unaffected, their V2 instructions validate the content
+//! when `validate_content` is set, which the typed `ParamsV2::new` constructors do.
+
+use std::{convert::TryFrom, fmt, str::FromStr};
+
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    address::{decode_address, decode_base58, decode_injective_address},
+    error::SnsRecordsError,
+    state::validation::{get_validation_length, Validation},
+    utils::{get_hashed_name, get_record_key_and_seeds},
+};
+
+/// Maximum length of the content of the text records
+pub const MAX_TEXT_LENGTH: usize = 512;
+/// Schemes accepted in the `url` record
+pub const URL_SCHEMES: [&str; 2] = ["http", "https"];
+/// Optional prefix of the `IPFS` record
+pub const IPFS_PREFIX: &str = "ipfs://";
+
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+/// Multibase prefix of the lowercase base32 encoding
+const CID_V1_BASE32_PREFIX: char = 'b';
+/// Base58 CIDv0 are SHA-256 multihashes: code 0x12, length 0x20, and the digest
+const CID_V0_PREFIX: &str = "Qm";
+const CID_V0_LEN: usize = 46;
+const CID_V0_MULTIHASH_PREFIX: [u8; 2] = [0x12, 0x20];
+
+macro_rules! records {
+    ($($(#[$doc:meta])* $variant:ident => $name:literal,)*) => {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum RecordName {
+            $($(#[$doc])* $variant,)*
+        }
+
+        impl RecordName {
+            pub const ALL: &'static [RecordName] = &[$(RecordName::$variant,)*];
+
+            /// The name of the record, from which its key is derived
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(RecordName::$variant => $name,)*
+                }
+            }
+        }
+
+        impl FromStr for RecordName {
+            type Err = SnsRecordsError;
+
+            /// Parses the exact name of a record, see `RecordName::find_misspelled` for near misses
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $($name => Ok(RecordName::$variant),)*
+                    _ => Err(SnsRecordsError::UnknownRecord),
+                }
+            }
+        }
+    };
+}
+
+records! {
+    /// IPFS CID of the content
+    Ipfs => "IPFS",
+    /// Arweave transaction ID of the content
+    Arwv => "ARWV",
+    /// Solana public key
+    Sol => "SOL",
+    /// Ethereum address
+    Eth => "ETH",
+    /// Bitcoin address
+    Btc => "BTC",
+    Ltc => "LTC",
+    Doge => "DOGE",
+    Email => "email",
+    Url => "url",
+    Discord => "discord",
+    Github => "github",
+    Reddit => "reddit",
+    Twitter => "twitter",
+    Telegram => "telegram",
+    /// URL of the profile picture
+    Pic => "pic",
+    Shdw => "SHDW",
+    Point => "POINT",
+    /// BNB Smart Chain address
+    Bsc => "BSC",
+    Injective => "INJ",
+    Backpack => "backpack",
+    /// IPv4 address
+    A => "A",
+    /// IPv6 address
+    Aaaa => "AAAA",
+    Cname => "CNAME",
+    Txt => "TXT",
+    /// URL of the profile background
+    Background => "background",
+    /// Base address
+    Base => "BASE",
+    Ipns => "IPNS",
+}
+
+impl RecordName {
+    /// Returns the record whose name only differs from `name` by its case, e.g. `SOL` for `SoL`
+    pub fn find_misspelled(name: &str) -> Option<RecordName> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|record| record.as_str() != name && record.as_str().eq_ignore_ascii_case(name))
+    }
+
+    pub fn hashed_name(&self) -> Vec<u8> {
+        get_hashed_name(self.as_str())
+    }
+
+    /// The key of the record account of `domain`
+    pub fn key(&self, domain: &Pubkey) -> Pubkey {
+        get_record_key_and_seeds(domain, self.as_str()).0
+    }
+}
+
+/// Checks the content of the `record` record, see `RecordName::check_content`
+///
+/// Custom record names are not validated
+pub fn check_record_content(record: &str, content: &[u8]) -> Result<(), SnsRecordsError> {
+    match RecordName::from_str(record) {
+        Ok(record) => record.check_content(content),
+        Err(_) => Ok(()),
+    }
+}
+
+impl RecordName {
+    /// Checks that `content` is well formed for the record
+    ///
+    /// - SOL: 32-byte public key
+    /// - ETH, BSC and BASE: 20-byte address
+    /// - BTC: UTF-8 encoded mainnet P2PKH or P2WPKH address
+    /// - INJ: UTF-8 encoded bech32 account address, `inj1...`
+    /// - IPFS: CIDv0 or base32 CIDv1, optionally prefixed with `ipfs://`
+    /// - A and AAAA: 4-byte IPv4 and 16-byte IPv6 addresses
+    /// - url: UTF-8 URL with one of the `URL_SCHEMES`, of at most `MAX_TEXT_LENGTH` bytes
+    /// - other records: UTF-8 text of at most `MAX_TEXT_LENGTH` bytes
+    pub fn check_content(&self, content: &[u8]) -> Result<(), SnsRecordsError> {
+        let is_valid = match self {
+            RecordName::Sol => content.len() == get_validation_length(Validation::Solana) as usize,
+            RecordName::Eth | RecordName::Bsc | RecordName::Base => {
+                content.len() == get_validation_length(Validation::Ethereum) as usize
+            }
+            RecordName::Btc => decode_address(*self, content).is_ok(),
+            RecordName::Injective => matches!(
+                std::str::from_utf8(content),
+                Ok(address) if decode_injective_address(address).is_ok()
+            ),
+            RecordName::A => content.len() == 4,
+            RecordName::Aaaa => content.len() == 16,
+            RecordName::Ipfs => matches!(
+                std::str::from_utf8(content),
+                Ok(cid) if is_valid_cid(cid.strip_prefix(IPFS_PREFIX).unwrap_or(cid))
+            ),
+            RecordName::Url => matches!(text_content(content), Some(url) if is_valid_url(url)),
+            _ => text_content(content).is_some(),
+        };
+        if !is_valid {
+            return Err(SnsRecordsError::InvalidRecordContent);
+        }
+        Ok(())
+    }
+}
+
+fn text_content(content: &[u8]) -> Option<&str> {
+    if content.len() > MAX_TEXT_LENGTH {
+        return None;
+    }
+    std::str::from_utf8(content).ok()
+}
+
+fn is_valid_url(url: &str) -> bool {
+    match url.split_once("://") {
+        Some((scheme, rest)) => {
+            URL_SCHEMES
+                .iter()
+                .any(|expected| scheme.eq_ignore_ascii_case(expected))
+                && !rest.is_empty()
+                && !url.contains(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+/// Reads an unsigned LEB128 integer, as used by the multiformats
+fn read_varint(bytes: &mut &[u8]) -> Option<u64> {
+    let mut value = 0u64;
+    // The multiformats restrict varints to 9 bytes
+    for i in 0..9 {
+        let (byte, rest) = bytes.split_first()?;
+        *bytes = rest;
+        value |= ((byte & 0x7f) as u64) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+fn decode_base32(input: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let (mut accumulator, mut bits) = (0u32, 0u32);
+    for c in input.bytes() {
+        let value = BASE32_ALPHABET.iter().position(|a| *a == c)? as u32;
+        accumulator = (accumulator << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((accumulator >> bits) as u8);
+            accumulator &= (1 << bits) - 1;
+        }
+    }
+    Some(bytes)
+}
+
+/// CIDv0 are base58 SHA-256 multihashes, CIDv1 are the version, the content codec and the
+/// multihash
+fn is_valid_cid(cid: &str) -> bool {
+    if cid.starts_with(CID_V0_PREFIX) && cid.len() == CID_V0_LEN {
+        return matches!(
+            decode_base58(cid),
+            Some(multihash) if multihash.len() == CID_V0_MULTIHASH_PREFIX.len() + 32
+                && multihash.starts_with(&CID_V0_MULTIHASH_PREFIX)
+        );
+    }
+    let decoded = match cid
+        .strip_prefix(CID_V1_BASE32_PREFIX)
+        .and_then(decode_base32)
+    {
+        Some(decoded) if decoded.len() <= MAX_TEXT_LENGTH => decoded,
+        _ => return false,
+    };
+    let bytes = &mut decoded.as_slice();
+    let is_valid_header = read_varint(bytes) == Some(1)
+        && read_varint(bytes).is_some()
+        && read_varint(bytes).is_some();
+    is_valid_header
+        && matches!(read_varint(bytes), Some(digest_len) if bytes.len() as u64 == digest_len)
+}
+
+impl fmt::Display for RecordName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl TryFrom<&str> for RecordName {
+    type Error = SnsRecordsError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::from_str(value)
+    }
+}
+
+impl From<RecordName> for String {
+    fn from(record: RecordName) -> Self {
+        record.as_str().to_owned()
+    }
+}
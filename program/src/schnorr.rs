@@ -0,0 +1,152 @@
+//! BIP-340 Schnorr signature verification
+//!
+//! The runtime does not expose Schnorr verification or elliptic curve arithmetic on secp256k1.
+//! The public nonce `R = s⋅G - e⋅P` is instead computed with the `secp256k1_recover` syscall,
+//! which returns `r⁻¹⋅(s'⋅R' - z'⋅G)` for a signature `(r, s')` of the hash `z'`. Recovering with
+//! `R' = P`, `r = x(P)`, `s' = -e⋅x(P)` and `z' = -s⋅x(P)` yields `s⋅G - e⋅P`.
+
+use solana_program::{hash::hashv, secp256k1_recover::secp256k1_recover};
+
+/// The order of the secp256k1 group, as little endian limbs
+const N: [u64; 4] = [
+    0xbfd25e8cd0364141,
+    0xbaaedce6af48a03b,
+    0xfffffffffffffffe,
+    0xffffffffffffffff,
+];
+
+/// A 256-bit integer, as little endian limbs
+type U256 = [u64; 4];
+
+fn from_be_bytes(bytes: &[u8; 32]) -> U256 {
+    let mut limbs = [0; 4];
+    for (limb, chunk) in limbs.iter_mut().rev().zip(bytes.chunks_exact(8)) {
+        let mut buffer = [0; 8];
+        buffer.copy_from_slice(chunk);
+        *limb = u64::from_be_bytes(buffer);
+    }
+    limbs
+}
+
+fn to_be_bytes(limbs: &U256) -> [u8; 32] {
+    let mut bytes = [0; 32];
+    for (chunk, limb) in bytes.chunks_exact_mut(8).zip(limbs.iter().rev()) {
+        chunk.copy_from_slice(&limb.to_be_bytes());
+    }
+    bytes
+}
+
+fn is_lower_than_n(a: &U256) -> bool {
+    for (a, n) in a.iter().zip(N.iter()).rev() {
+        if a != n {
+            return a < n;
+        }
+    }
+    false
+}
+
+fn wrapping_add(a: &U256, b: &U256) -> (U256, bool) {
+    let mut result = [0; 4];
+    let mut carry = false;
+    for i in 0..4 {
+        let (sum, c1) = a[i].overflowing_add(b[i]);
+        let (sum, c2) = sum.overflowing_add(carry as u64);
+        result[i] = sum;
+        carry = c1 || c2;
+    }
+    (result, carry)
+}
+
+fn wrapping_sub(a: &U256, b: &U256) -> U256 {
+    let mut result = [0; 4];
+    let mut borrow = false;
+    for i in 0..4 {
+        let (diff, b1) = a[i].overflowing_sub(b[i]);
+        let (diff, b2) = diff.overflowing_sub(borrow as u64);
+        result[i] = diff;
+        borrow = b1 || b2;
+    }
+    result
+}
+
+/// Reduces a 256-bit integer modulo n, a single subtraction is enough as 2^256 < 2n
+fn reduce(a: &U256) -> U256 {
+    if is_lower_than_n(a) {
+        *a
+    } else {
+        wrapping_sub(a, &N)
+    }
+}
+
+/// `a + b mod n` for `a, b < n`
+fn add_mod(a: &U256, b: &U256) -> U256 {
+    let (sum, carry) = wrapping_add(a, b);
+    if carry || !is_lower_than_n(&sum) {
+        wrapping_sub(&sum, &N)
+    } else {
+        sum
+    }
+}
+
+/// `a⋅b mod n` for `a, b < n`
+fn mul_mod(a: &U256, b: &U256) -> U256 {
+    let mut result = [0; 4];
+    for i in (0..256).rev() {
+        result = add_mod(&result, &result);
+        if (b[i / 64] >> (i % 64)) & 1 == 1 {
+            result = add_mod(&result, a);
+        }
+    }
+    result
+}
+
+/// `-a mod n` for `a < n`
+fn neg_mod(a: &U256) -> U256 {
+    if a == &[0; 4] {
+        *a
+    } else {
+        wrapping_sub(&N, a)
+    }
+}
+
+pub fn tagged_hash(tag: &[u8], data: &[&[u8]]) -> [u8; 32] {
+    let tag_hash = hashv(&[tag]);
+    let mut input = vec![tag_hash.as_ref(), tag_hash.as_ref()];
+    input.extend_from_slice(data);
+    hashv(&input).to_bytes()
+}
+
+/// Verifies the BIP-340 signature of `message` by the x-only public key `pubkey`
+pub fn verify(pubkey: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> bool {
+    let mut r = [0; 32];
+    r.copy_from_slice(&signature[..32]);
+    let mut s = [0; 32];
+    s.copy_from_slice(&signature[32..]);
+
+    let s = from_be_bytes(&s);
+    let px = from_be_bytes(pubkey);
+    // x(P) is used as the ECDSA r value which has to be lower than n
+    if !is_lower_than_n(&s) || !is_lower_than_n(&px) {
+        return false;
+    }
+
+    let e = reduce(&from_be_bytes(&tagged_hash(
+        b"BIP0340/challenge",
+        &[&r, pubkey, message],
+    )));
+
+    let recovery_s = neg_mod(&mul_mod(&e, &px));
+    let recovery_z = neg_mod(&mul_mod(&s, &px));
+
+    let mut recovery_signature = [0; 64];
+    recovery_signature[..32].copy_from_slice(pubkey);
+    recovery_signature[32..].copy_from_slice(&to_be_bytes(&recovery_s));
+
+    // The recovery ID 0 selects the point with an even y coordinate, as in `lift_x`
+    let nonce = match secp256k1_recover(&to_be_bytes(&recovery_z), 0, &recovery_signature) {
+        Ok(nonce) => nonce.0,
+        Err(_) => return false,
+    };
+
+    nonce[..32] == r && nonce[63] & 1 == 0
+}
@@ -1,2 +1,5 @@
+pub mod record;
 pub mod record_header;
+pub mod record_view;
 pub mod validation;
+pub mod xchain_nonce;
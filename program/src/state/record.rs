@@ -0,0 +1,77 @@
+use crate::error::SnsRecordsError;
+
+use super::{
+    record_header::{RecordHeader, VersionedRecordHeader},
+    record_view::RecordView,
+    validation::{decode_xchain_staleness_id, get_validation_length, Validation},
+};
+
+/// Owned record account, the counterpart of the `Record` class of the JS bindings
+#[derive(Clone, Debug)]
+pub struct Record {
+    header: VersionedRecordHeader,
+    staleness_validation: Validation,
+    right_of_association_validation: Validation,
+    /// The data following the record header
+    data: Vec<u8>,
+}
+
+impl Record {
+    /// Deserializes the data of a record account, including its name registry header
+    pub fn deserialize(buffer: &[u8]) -> Result<Self, SnsRecordsError> {
+        let view = RecordView::new(buffer)?;
+        Ok(Self {
+            header: view.versioned_header(),
+            staleness_validation: view.staleness_validation(),
+            right_of_association_validation: view.right_of_association_validation(),
+            data: view.data().to_vec(),
+        })
+    }
+
+    pub fn header(&self) -> RecordHeader {
+        self.header.header()
+    }
+
+    pub fn versioned_header(&self) -> VersionedRecordHeader {
+        self.header
+    }
+
+    /// The data following the record header
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn staleness_validation(&self) -> Validation {
+        self.staleness_validation
+    }
+
+    pub fn right_of_association_validation(&self) -> Validation {
+        self.right_of_association_validation
+    }
+
+    pub fn staleness_id(&self) -> &[u8] {
+        &self.data[..self.staleness_id_len()]
+    }
+
+    pub fn roa_id(&self) -> &[u8] {
+        &self.data[self.staleness_id_len()..self.content_offset()]
+    }
+
+    pub fn content(&self) -> &[u8] {
+        &self.data[self.content_offset()..]
+    }
+
+    /// Decodes a `Validation::XChain` staleness ID, see `decode_xchain_staleness_id`
+    pub fn xchain_staleness_id(&self) -> Result<(u16, [u8; 32]), SnsRecordsError> {
+        decode_xchain_staleness_id(self.staleness_validation, self.staleness_id())
+    }
+
+    fn staleness_id_len(&self) -> usize {
+        get_validation_length(self.staleness_validation) as usize
+    }
+
+    fn content_offset(&self) -> usize {
+        self.staleness_id_len()
+            + get_validation_length(self.right_of_association_validation) as usize
+    }
+}
@@ -1,7 +1,9 @@
 use solana_program::program_pack::Pack;
 use spl_name_service::state::NameRecordHeader;
 
-use super::validation::Validation;
+use crate::error::SnsRecordsError;
+
+use super::validation::ValidationState;
 
 use bytemuck::{Pod, Zeroable};
 
@@ -17,17 +19,188 @@ pub struct RecordHeader {
 impl RecordHeader {
     pub const LEN: usize = std::mem::size_of::<Self>();
 
+    /// Reads the header of the data of a record account, in any layout
+    ///
+    /// Panics if the header is invalid, see `RecordHeader::try_from_buffer`
+    #[deprecated(note = "use `RecordHeader::try_from_buffer`, which returns an error instead")]
     pub fn from_buffer(buffer: &[u8]) -> Self {
-        let offset = NameRecordHeader::LEN;
-        let (_, data) = buffer.split_at(offset);
-        *bytemuck::from_bytes::<Self>(&data[..Self::LEN])
+        Self::try_from_buffer(buffer).unwrap()
+    }
+
+    /// Reads the header of the data of a record account, in any layout
+    pub fn try_from_buffer(buffer: &[u8]) -> Result<Self, SnsRecordsError> {
+        VersionedRecordHeader::try_from_buffer(buffer).map(|header| header.header())
     }
 
+    /// The header of a record holding `content_length` bytes, after a `ValidationEvent::Reset`
     pub fn new(content_length: u32) -> Self {
+        let mut header = Self {
+            staleness_validation: 0,
+            right_of_association_validation: 0,
+            content_length,
+        };
+        header.set_validation_state(ValidationState::RESET);
+        header
+    }
+
+    pub fn set_validation_state(&mut self, state: ValidationState) {
+        self.staleness_validation = state.staleness as u16;
+        self.right_of_association_validation = state.right_of_association as u16;
+    }
+}
+
+/// Stored in place of the staleness validation of a V1 header, which it can never be
+pub const RECORD_HEADER_V2_MARKER: u16 = u16::MAX;
+
+#[derive(Clone, Copy, Zeroable, Pod, Debug)]
+#[allow(missing_docs)]
+#[repr(C)]
+pub struct RecordHeaderV2 {
+    /// Always `RECORD_HEADER_V2_MARKER`
+    pub marker: u16,
+    pub version: u8,
+    pub padding: u8,
+    /// See `RecordHeaderV2::FLAG_PENDING_UPLOAD`
+    pub flags: u32,
+    pub staleness_validation: u16,
+    pub right_of_association_validation: u16,
+    pub content_length: u32,
+}
+
+impl RecordHeaderV2 {
+    pub const LEN: usize = std::mem::size_of::<Self>();
+
+    pub const VERSION: u8 = 2;
+
+    /// Set between `BeginUpload` and `FinalizeUpload`, while the content is written in chunks
+    pub const FLAG_PENDING_UPLOAD: u32 = 1;
+
+    pub fn new(header: RecordHeader, flags: u32) -> Self {
         Self {
-            staleness_validation: Validation::None as u16,
-            right_of_association_validation: Validation::None as u16,
+            marker: RECORD_HEADER_V2_MARKER,
+            version: Self::VERSION,
+            padding: 0,
+            flags,
+            staleness_validation: header.staleness_validation,
+            right_of_association_validation: header.right_of_association_validation,
+            content_length: header.content_length,
+        }
+    }
+
+    /// The fields shared with the V1 header
+    pub fn header(&self) -> RecordHeader {
+        RecordHeader {
+            staleness_validation: self.staleness_validation,
+            right_of_association_validation: self.right_of_association_validation,
+            content_length: self.content_length,
+        }
+    }
+}
+
+/// Header of a record account in one of the supported layouts
+///
+/// New records use the V1 layout, the `MigrateRecord` instruction upgrades them to the V2 layout.
+/// Instructions rewriting the header keep the layout of the record.
+#[derive(Clone, Copy, Debug)]
+pub enum VersionedRecordHeader {
+    V1(RecordHeader),
+    V2(RecordHeaderV2),
+}
+
+impl VersionedRecordHeader {
+    /// Reads the header of the data of a record account
+    pub fn try_from_buffer(buffer: &[u8]) -> Result<Self, SnsRecordsError> {
+        let marker = buffer
+            .get(NameRecordHeader::LEN..NameRecordHeader::LEN + 2)
+            .ok_or(SnsRecordsError::OutOfBound)?;
+        if u16::from_le_bytes([marker[0], marker[1]]) != RECORD_HEADER_V2_MARKER {
+            return read_header(buffer).map(Self::V1);
+        }
+
+        let header: RecordHeaderV2 = read_header(buffer)?;
+        if header.version != RecordHeaderV2::VERSION {
+            return Err(SnsRecordsError::DataTypeMismatch);
+        }
+        Ok(Self::V2(header))
+    }
+
+    /// The length of the header in bytes
+    pub fn size(&self) -> usize {
+        match self {
+            Self::V1(_) => RecordHeader::LEN,
+            Self::V2(_) => RecordHeaderV2::LEN,
+        }
+    }
+
+    pub fn header(&self) -> RecordHeader {
+        match self {
+            Self::V1(header) => *header,
+            Self::V2(header) => header.header(),
+        }
+    }
+
+    /// Replaces the fields shared by all layouts, keeping the layout and its other fields
+    pub fn with_header(self, header: RecordHeader) -> Self {
+        match self {
+            Self::V1(_) => Self::V1(header),
+            Self::V2(current) => Self::V2(RecordHeaderV2::new(header, current.flags)),
+        }
+    }
+
+    /// The header of a record whose content is replaced by `content_length` bytes, the
+    /// validations are reset and a pending upload is cancelled
+    pub fn edit_content(self, content_length: u32) -> Self {
+        match self {
+            Self::V1(_) => Self::V1(RecordHeader::new(content_length)),
+            Self::V2(current) => Self::V2(RecordHeaderV2::new(
+                RecordHeader::new(content_length),
+                current.flags & !RecordHeaderV2::FLAG_PENDING_UPLOAD,
+            )),
+        }
+    }
+
+    /// The header of a record pending a chunked upload, uploads always use the V2 layout
+    pub fn pending_upload() -> Self {
+        Self::V2(RecordHeaderV2::new(
+            RecordHeader::new(0),
+            RecordHeaderV2::FLAG_PENDING_UPLOAD,
+        ))
+    }
+
+    pub fn is_pending_upload(&self) -> bool {
+        matches!(
+            self,
+            Self::V2(header) if header.flags & RecordHeaderV2::FLAG_PENDING_UPLOAD != 0
+        )
+    }
+
+    /// The header of a record whose upload of `content_length` bytes is finalized, the
+    /// validations are unchanged
+    pub fn finalize_upload(self, content_length: u32) -> Self {
+        let header = RecordHeader {
             content_length,
+            ..self.header()
+        };
+        match self {
+            Self::V1(_) => Self::V1(header),
+            Self::V2(current) => Self::V2(RecordHeaderV2::new(
+                header,
+                current.flags & !RecordHeaderV2::FLAG_PENDING_UPLOAD,
+            )),
         }
     }
+
+    pub fn bytes(&self) -> &[u8] {
+        match self {
+            Self::V1(header) => bytemuck::bytes_of(header),
+            Self::V2(header) => bytemuck::bytes_of(header),
+        }
+    }
+}
+
+fn read_header<T: Pod>(buffer: &[u8]) -> Result<T, SnsRecordsError> {
+    let data = buffer
+        .get(NameRecordHeader::LEN..NameRecordHeader::LEN + std::mem::size_of::<T>())
+        .ok_or(SnsRecordsError::OutOfBound)?;
+    bytemuck::try_pod_read_unaligned(data).map_err(|_| SnsRecordsError::OutOfBound)
 }
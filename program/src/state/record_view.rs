@@ -0,0 +1,116 @@
+use std::convert::TryFrom;
+
+use solana_program::program_pack::Pack;
+use spl_name_service::state::NameRecordHeader;
+
+use crate::error::SnsRecordsError;
+
+use super::{
+    record_header::{RecordHeader, VersionedRecordHeader},
+    validation::{get_validation_length, Validation, ValidationState},
+};
+
+/// Checked view over the data of a record account
+///
+/// The data following the name registry header is laid out as
+/// | record header | staleness ID | RoA ID | content |
+///
+/// The record header can be in any of the layouts of `VersionedRecordHeader`
+#[derive(Clone, Copy, Debug)]
+pub struct RecordView<'a> {
+    header: VersionedRecordHeader,
+    staleness_validation: Validation,
+    right_of_association_validation: Validation,
+    buffer: &'a [u8],
+    staleness_id: &'a [u8],
+    roa_id: &'a [u8],
+    content: &'a [u8],
+}
+
+impl<'a> RecordView<'a> {
+    /// Parses the data of a record account, including its name registry header
+    pub fn new(data: &'a [u8]) -> Result<Self, SnsRecordsError> {
+        let header = VersionedRecordHeader::try_from_buffer(data)?;
+        let staleness_validation = Validation::try_from(header.header().staleness_validation)?;
+        let right_of_association_validation =
+            Validation::try_from(header.header().right_of_association_validation)?;
+
+        let buffer = data
+            .get(NameRecordHeader::LEN + header.size()..)
+            .ok_or(SnsRecordsError::OutOfBound)?;
+        let (staleness_id, rest) =
+            checked_split(buffer, get_validation_length(staleness_validation) as usize)?;
+        let (roa_id, content) = checked_split(
+            rest,
+            get_validation_length(right_of_association_validation) as usize,
+        )?;
+
+        Ok(Self {
+            header,
+            staleness_validation,
+            right_of_association_validation,
+            buffer,
+            staleness_id,
+            roa_id,
+            content,
+        })
+    }
+
+    pub fn header(&self) -> RecordHeader {
+        self.header.header()
+    }
+
+    pub fn versioned_header(&self) -> VersionedRecordHeader {
+        self.header
+    }
+
+    pub fn staleness_validation(&self) -> Validation {
+        self.staleness_validation
+    }
+
+    pub fn right_of_association_validation(&self) -> Validation {
+        self.right_of_association_validation
+    }
+
+    pub fn validation_state(&self) -> ValidationState {
+        ValidationState {
+            staleness: self.staleness_validation,
+            right_of_association: self.right_of_association_validation,
+        }
+    }
+
+    pub fn staleness_id(&self) -> &'a [u8] {
+        self.staleness_id
+    }
+
+    pub fn roa_id(&self) -> &'a [u8] {
+        self.roa_id
+    }
+
+    /// The data following the validation IDs, up to the end of the account
+    pub fn content(&self) -> &'a [u8] {
+        self.content
+    }
+
+    /// The data following the staleness ID, regardless of the RoA validation
+    pub fn after_staleness_id(&self) -> &'a [u8] {
+        &self.buffer[self.staleness_id.len()..]
+    }
+
+    /// The data following the record header
+    pub fn data(&self) -> &'a [u8] {
+        self.buffer
+    }
+
+    /// The length of the data following the record header
+    pub fn data_len(&self) -> usize {
+        self.buffer.len()
+    }
+}
+
+fn checked_split(buffer: &[u8], mid: usize) -> Result<(&[u8], &[u8]), SnsRecordsError> {
+    if mid > buffer.len() {
+        return Err(SnsRecordsError::OutOfBound);
+    }
+    Ok(buffer.split_at(mid))
+}
@@ -1,11 +1,13 @@
 use std::convert::TryFrom;
 
+use crate::error::SnsRecordsError;
+
 use {
     bonfida_utils::BorshSize,
     borsh::{BorshDeserialize, BorshSerialize},
 };
 
-#[derive(BorshDeserialize, BorshSerialize, BorshSize, Clone, Copy, Debug)]
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u16)]
 pub enum Validation {
     None,
@@ -13,6 +15,38 @@ pub enum Validation {
     Ethereum,
     UnverifiedSolana,
     XChain,
+    Bitcoin,
+    Nostr,
+    Passkey,
+    UnverifiedEthereum,
+    UnverifiedBitcoin,
+    UnverifiedNostr,
+    UnverifiedPasskey,
+}
+
+impl Validation {
+    /// The unverified kind declaring a RoA ID which must later be proven with this validation
+    pub fn unverified(self) -> Option<Validation> {
+        match self {
+            Validation::Solana => Some(Validation::UnverifiedSolana),
+            Validation::Ethereum => Some(Validation::UnverifiedEthereum),
+            Validation::Bitcoin => Some(Validation::UnverifiedBitcoin),
+            Validation::Nostr => Some(Validation::UnverifiedNostr),
+            Validation::Passkey => Some(Validation::UnverifiedPasskey),
+            _ => None,
+        }
+    }
+
+    pub fn is_unverified(self) -> bool {
+        matches!(
+            self,
+            Validation::UnverifiedSolana
+                | Validation::UnverifiedEthereum
+                | Validation::UnverifiedBitcoin
+                | Validation::UnverifiedNostr
+                | Validation::UnverifiedPasskey
+        )
+    }
 }
 
 impl TryFrom<u16> for Validation {
@@ -24,17 +58,136 @@ impl TryFrom<u16> for Validation {
             2 => Ok(Validation::Ethereum),
             3 => Ok(Validation::UnverifiedSolana),
             4 => Ok(Validation::XChain),
+            5 => Ok(Validation::Bitcoin),
+            6 => Ok(Validation::Nostr),
+            7 => Ok(Validation::Passkey),
+            8 => Ok(Validation::UnverifiedEthereum),
+            9 => Ok(Validation::UnverifiedBitcoin),
+            10 => Ok(Validation::UnverifiedNostr),
+            11 => Ok(Validation::UnverifiedPasskey),
             _ => Err(crate::error::SnsRecordsError::DataTypeMismatch),
         }
     }
 }
 
+/// The staleness and RoA validations of a record
+///
+/// Validations only change through `ValidationState::transition`:
+/// - the staleness is `None` or proven by the domain owner (`Solana` or `XChain`)
+/// - the RoA goes from `None` to a declared unverified kind, then to its verified counterpart,
+///   kinds proven by a signed message can also be verified without being declared
+/// - a verified RoA must be cleared before another kind is declared or verified
+/// - changing the staleness or the content clears the RoA
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValidationState {
+    pub staleness: Validation,
+    pub right_of_association: Validation,
+}
+
+/// The changes of the validations of a record
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValidationEvent {
+    /// The content is replaced, both validations are cleared
+    Reset,
+    /// The domain owner proves the staleness with the given validation, the RoA is cleared
+    ValidateStaleness(Validation),
+    /// The domain owner declares the RoA ID of the given unverified kind
+    DeclareRoa(Validation),
+    /// The owner of the RoA ID proves the RoA with the given validation
+    ValidateRoa(Validation),
+    /// The RoA is removed
+    ClearRoa,
+}
+
+impl ValidationState {
+    pub const RESET: Self = Self {
+        staleness: Validation::None,
+        right_of_association: Validation::None,
+    };
+
+    /// Returns the state following `event`, or `InvalidValidationTransition` if it is not allowed
+    pub fn transition(self, event: ValidationEvent) -> Result<Self, SnsRecordsError> {
+        let next = match event {
+            ValidationEvent::Reset => Some(Self::RESET),
+            ValidationEvent::ValidateStaleness(staleness) => {
+                matches!(staleness, Validation::Solana | Validation::XChain).then_some(Self {
+                    staleness,
+                    right_of_association: Validation::None,
+                })
+            }
+            ValidationEvent::DeclareRoa(roa) => (roa.is_unverified()
+                && (self.right_of_association == Validation::None
+                    || self.right_of_association.is_unverified()))
+            .then_some(Self {
+                right_of_association: roa,
+                ..self
+            }),
+            ValidationEvent::ValidateRoa(roa) => {
+                let current = self.right_of_association;
+                let is_allowed_from = current == roa
+                    || Some(current) == roa.unverified()
+                    // Solana verifiers sign the transaction rather than a message naming their
+                    // key, which must therefore be declared beforehand
+                    || (current == Validation::None && roa != Validation::Solana);
+                (roa.unverified().is_some() && is_allowed_from).then_some(Self {
+                    right_of_association: roa,
+                    ..self
+                })
+            }
+            ValidationEvent::ClearRoa => Some(Self {
+                right_of_association: Validation::None,
+                ..self
+            }),
+        };
+        next.ok_or(SnsRecordsError::InvalidValidationTransition)
+    }
+}
+
 pub fn get_validation_length(validation: Validation) -> u32 {
     match validation {
         Validation::None => 0,
-        Validation::Ethereum => 20,
+        Validation::Ethereum | Validation::UnverifiedEthereum => 20,
         Validation::Solana => 32,
         Validation::UnverifiedSolana => 32,
         Validation::XChain => 34,
+        // Address type followed by the HASH160 of the public key
+        Validation::Bitcoin | Validation::UnverifiedBitcoin => 21,
+        // BIP-340 x-only public key
+        Validation::Nostr | Validation::UnverifiedNostr => 32,
+        // Compressed P-256 public key
+        Validation::Passkey | Validation::UnverifiedPasskey => 33,
     }
 }
+
+/// Length of the Wormhole chain ID of a `Validation::XChain` staleness ID
+const XCHAIN_CHAIN_ID_LEN: usize = 2;
+
+/// Decodes a `Validation::XChain` staleness ID into the Wormhole chain ID and address of the
+/// owner, the chain ID is encoded in little endian
+pub fn decode_xchain_staleness_id(
+    staleness_validation: Validation,
+    staleness_id: &[u8],
+) -> Result<(u16, [u8; 32]), SnsRecordsError> {
+    if staleness_validation != Validation::XChain {
+        return Err(SnsRecordsError::UnsupportedValidation);
+    }
+    if staleness_id.len() != get_validation_length(Validation::XChain) as usize {
+        return Err(SnsRecordsError::OutOfBound);
+    }
+    let (chain_id, address) = staleness_id.split_at(XCHAIN_CHAIN_ID_LEN);
+    let mut owner_address = [0; 32];
+    owner_address.copy_from_slice(address);
+    Ok((
+        u16::from_le_bytes([chain_id[0], chain_id[1]]),
+        owner_address,
+    ))
+}
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum BitcoinAddressType {
+    /// Pay to public key hash (legacy `1...` addresses)
+    P2pkh,
+    /// Pay to witness public key hash (native SegWit `bc1q...` addresses)
+    P2wpkh,
+}
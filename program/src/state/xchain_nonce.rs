@@ -0,0 +1,24 @@
+use {
+    bonfida_utils::BorshSize,
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::pubkey::Pubkey,
+};
+
+/// The nonce of the next operation authorized by the foreign owner of a XChain domain
+///
+/// Stored in a program account derived from the domain key, created on the first operation
+#[derive(
+    BorshDeserialize, BorshSerialize, BorshSize, Clone, Copy, Debug, Default, PartialEq, Eq,
+)]
+pub struct XChainNonce {
+    pub nonce: u64,
+}
+
+impl XChainNonce {
+    pub const SEED: &'static [u8; 12] = b"xchain_nonce";
+    pub const LEN: usize = 8;
+
+    pub fn find_key(domain: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::SEED, domain.as_ref()], &crate::ID)
+    }
+}
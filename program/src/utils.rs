@@ -1,15 +1,21 @@
-use solana_program::{account_info::AccountInfo, hash::hashv, program_pack::Pack};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, hash::hashv, program_pack::Pack,
+};
 use spl_name_service::state::NameRecordHeader;
 use spl_name_service::state::HASH_PREFIX;
 
-use crate::error::SnsRecordsError;
+use crate::{
+    cpi,
+    error::SnsRecordsError,
+    state::{
+        record_header::VersionedRecordHeader, record_view::RecordView, validation::ValidationEvent,
+    },
+};
 
 use {
     solana_program::pubkey, solana_program::pubkey::Pubkey,
-    spl_name_service::state::get_seeds_and_key,
+    spl_name_service::state::get_seeds_and_key, std::convert::TryInto,
 };
- 
-
 
 #[cfg(feature = "devnet")]
 pub const ROOT_DOMAIN: Pubkey = pubkey!("5eoDkP6vCQBXqDV9YN2NdUs3nmML3dMRNmEYpiyVNBm2");
@@ -64,3 +70,66 @@ pub fn check_domain_parent(
 
     Ok(())
 }
+
+/// Fails if the record is pending an upload, its content is only partially written
+pub fn check_not_pending_upload(record: &AccountInfo) -> Result<(), SnsRecordsError> {
+    let header = VersionedRecordHeader::try_from_buffer(&record.data.borrow())?;
+    if header.is_pending_upload() {
+        return Err(SnsRecordsError::UploadPending);
+    }
+    Ok(())
+}
+
+/// Replaces the RoA ID of the record, resizing it if needed, and applies the RoA `event` to its
+/// validations
+///
+/// Fails if the record declares a different RoA ID for the unverified kind of the validation
+pub fn update_roa<'a>(
+    record: &AccountInfo<'a>,
+    central_state: &AccountInfo<'a>,
+    fee_payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    event: ValidationEvent,
+    roa_id: &[u8],
+) -> ProgramResult {
+    let (new_buffer, current_length, header) = {
+        let record_data = record.data.borrow();
+        let view = RecordView::new(&record_data)?;
+        let state = view.validation_state().transition(event)?;
+
+        // A RoA ID declared by the domain owner can only be proven by its own key
+        if let ValidationEvent::ValidateRoa(validation) = event {
+            if validation.unverified() == Some(view.right_of_association_validation())
+                && view.roa_id() != roa_id
+            {
+                return Err(SnsRecordsError::RoaMismatch.into());
+            }
+        }
+
+        let mut header = view.header();
+        header.set_validation_state(state);
+        let new_buffer = [view.staleness_id(), roa_id, view.content()].concat();
+
+        (
+            new_buffer,
+            view.data_len(),
+            view.versioned_header().with_header(header),
+        )
+    };
+
+    if new_buffer.len() != current_length {
+        cpi::resize_record(
+            record,
+            central_state,
+            fee_payer,
+            system_program,
+            (new_buffer.len() + header.size())
+                .try_into()
+                .map_err(|_| SnsRecordsError::NumericalOverflow)?,
+        )?;
+    }
+
+    let data = [header.bytes(), &new_buffer].concat();
+
+    cpi::edit_record(&data, 0, record, central_state)
+}
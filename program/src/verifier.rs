@@ -0,0 +1,86 @@
+//! Signature verifiers of the right of association
+//!
+//! Only the Ethereum, Bitcoin and Nostr validations have a verifier. The per-chain validate
+//! instructions remain the canonical paths: `ValidateSignature` covers these three validations
+//! only, Solana, Ed25519 and passkey RoAs still need their own instructions since the
+//! signatures are checked by the runtime or the precompiles, and EIP-712 Ethereum signatures are
+//! only supported by `ValidateEthereumSignature`.
+
+use {
+    crate::{
+        error::SnsRecordsError,
+        processor::{
+            validate_bitcoin_signature::BitcoinVerifier,
+            validate_ethereum_signature::EthereumVerifier, validate_nostr_signature::NostrVerifier,
+        },
+        state::{
+            record_view::RecordView,
+            validation::{decode_xchain_staleness_id, Validation},
+        },
+    },
+    solana_program::{entrypoint::ProgramResult, program_error::ProgramError, pubkey::Pubkey},
+    std::convert::TryInto,
+};
+
+/// The parts of a record covered by a RoA signature
+pub struct SignedRecord<'a> {
+    pub record_key: &'a Pubkey,
+    pub staleness_validation: Validation,
+    pub staleness_id: &'a [u8],
+    pub content: &'a [u8],
+}
+
+impl<'a> SignedRecord<'a> {
+    /// Parses the data of a record account, skipping its current RoA ID
+    pub fn from_record_data(record_key: &'a Pubkey, data: &'a [u8]) -> Result<Self, ProgramError> {
+        let record = RecordView::new(data)?;
+        Ok(Self {
+            record_key,
+            staleness_validation: record.staleness_validation(),
+            staleness_id: record.staleness_id(),
+            content: record.content(),
+        })
+    }
+
+    /// The staleness ID as a public key
+    ///
+    /// Implicitly means that if the staleness is not verified it's impossible to verify the RoA
+    pub fn staleness_key(&self) -> Result<Pubkey, SnsRecordsError> {
+        let staleness_id: [u8; 32] = self
+            .staleness_id
+            .try_into()
+            .map_err(|_| SnsRecordsError::OutOfBound)?;
+        Ok(Pubkey::from(staleness_id))
+    }
+
+    /// Decodes a `Validation::XChain` staleness ID, see `decode_xchain_staleness_id`
+    pub fn xchain_staleness_id(&self) -> Result<(u16, [u8; 32]), SnsRecordsError> {
+        decode_xchain_staleness_id(self.staleness_validation, self.staleness_id)
+    }
+}
+
+pub trait SignatureVerifier {
+    /// The length of the RoA ID
+    fn id_len(&self) -> usize;
+
+    /// The message signed by the owner of the RoA ID
+    fn build_message(&self, record: &SignedRecord) -> Result<Vec<u8>, ProgramError>;
+
+    /// Verifies the signature of the message by the owner of the RoA ID
+    fn verify(&self, message: &[u8], signature: &[u8], id: &[u8]) -> ProgramResult;
+}
+
+/// Returns the verifier of RoA signatures for the validation
+///
+/// Ed25519 and P-256 signatures cannot be verified by the program itself, `Solana` and `Passkey`
+/// RoAs go through the precompile instructions and return `UnsupportedValidation` here
+pub fn get_verifier(
+    validation: Validation,
+) -> Result<&'static dyn SignatureVerifier, SnsRecordsError> {
+    match validation {
+        Validation::Ethereum => Ok(&EthereumVerifier),
+        Validation::Bitcoin => Ok(&BitcoinVerifier),
+        Validation::Nostr => Ok(&NostrVerifier),
+        _ => Err(SnsRecordsError::UnsupportedValidation),
+    }
+}
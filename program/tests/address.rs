@@ -0,0 +1,145 @@
+use sns_records::{
+    address::{
+        check_address_roa, decode_address, decode_bitcoin_address, decode_injective_address,
+    },
+    error::SnsRecordsError,
+    record::RecordName,
+    state::validation::{BitcoinAddressType, Validation},
+};
+
+// BIP-173 test vector
+const P2WPKH_ADDRESS: &str = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+const P2WPKH_HASH: &str = "751e76e8199196d454941c45d1b3a323f1433bd6";
+const P2PKH_ADDRESS: &str = "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2";
+const P2PKH_HASH: &str = "77bff20c60e522dfaa3350c39b030a5d004e839a";
+
+#[test]
+fn test_decode_injective_address() {
+    // Injective account of the Ethereum address 0xaf79152ac5df276d9a8e1e2e22822f9713474902
+    let address = decode_injective_address("inj14au322k9munkmx5wrchz9q30juf5wjgz2cfqku").unwrap();
+    assert_eq!(
+        hex::encode(address),
+        "af79152ac5df276d9a8e1e2e22822f9713474902"
+    );
+
+    for invalid in [
+        // Invalid checksum
+        "inj14au322k9munkmx5wrchz9q30juf5wjgz2cfqkv",
+        // Other human readable part
+        P2WPKH_ADDRESS,
+        "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu",
+        "inj1qqqsyqcyq5rqwzqf",
+    ] {
+        assert!(matches!(
+            decode_injective_address(invalid),
+            Err(SnsRecordsError::InvalidAddress)
+        ));
+    }
+}
+
+#[test]
+fn test_decode_bitcoin_address() {
+    let (address_type, hash) = decode_bitcoin_address(P2WPKH_ADDRESS).unwrap();
+    assert_eq!(address_type, BitcoinAddressType::P2wpkh);
+    assert_eq!(hex::encode(hash), P2WPKH_HASH);
+    let (_, hash) = decode_bitcoin_address(&P2WPKH_ADDRESS.to_uppercase()).unwrap();
+    assert_eq!(hex::encode(hash), P2WPKH_HASH);
+
+    let (address_type, hash) = decode_bitcoin_address(P2PKH_ADDRESS).unwrap();
+    assert_eq!(address_type, BitcoinAddressType::P2pkh);
+    assert_eq!(hex::encode(hash), P2PKH_HASH);
+
+    for invalid in [
+        // Invalid checksums
+        "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5",
+        "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN3",
+        // Mixed case
+        "bc1qW508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+        // Testnet
+        "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx",
+        // P2SH
+        "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy",
+        // Version 1 witness program
+        "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7k7grplx",
+        "",
+    ] {
+        assert!(
+            matches!(
+                decode_bitcoin_address(invalid),
+                Err(SnsRecordsError::InvalidAddress)
+            ),
+            "{}",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn test_check_address_roa() {
+    let sol_address = [1; 32];
+    check_address_roa(
+        RecordName::Sol,
+        Validation::Solana,
+        &sol_address,
+        &sol_address,
+    )
+    .unwrap();
+
+    let eth_address = [2; 20];
+    check_address_roa(
+        RecordName::Eth,
+        Validation::Ethereum,
+        &eth_address,
+        &eth_address,
+    )
+    .unwrap();
+
+    let btc_roa_id = [
+        &[BitcoinAddressType::P2wpkh as u8][..],
+        &hex::decode(P2WPKH_HASH).unwrap(),
+    ]
+    .concat();
+    assert_eq!(
+        decode_address(RecordName::Btc, P2WPKH_ADDRESS.as_bytes()).unwrap(),
+        btc_roa_id
+    );
+    check_address_roa(
+        RecordName::Btc,
+        Validation::Bitcoin,
+        &btc_roa_id,
+        P2WPKH_ADDRESS.as_bytes(),
+    )
+    .unwrap();
+
+    // The RoA must be verified for the address of the content
+    for (validation, roa_id) in [
+        (Validation::Solana, [3; 32].to_vec()),
+        (Validation::UnverifiedSolana, sol_address.to_vec()),
+        (Validation::Ethereum, sol_address.to_vec()),
+    ] {
+        assert!(matches!(
+            check_address_roa(RecordName::Sol, validation, &roa_id, &sol_address),
+            Err(SnsRecordsError::AddressRoaMismatch)
+        ));
+    }
+    assert!(matches!(
+        check_address_roa(
+            RecordName::Eth,
+            Validation::Ethereum,
+            &eth_address,
+            b"0x0202"
+        ),
+        Err(SnsRecordsError::InvalidAddress)
+    ));
+
+    // Only address records are supported
+    assert!(matches!(
+        check_address_roa(
+            RecordName::Url,
+            Validation::Solana,
+            &sol_address,
+            &sol_address
+        ),
+        Err(SnsRecordsError::UnsupportedValidation)
+    ));
+}
@@ -1,12 +1,16 @@
 use std::str::FromStr;
 
+use borsh::ser::BorshSerialize;
+use sns_records::entrypoint::process_instruction;
+use sns_warp_common::state::x_domain::XDomain;
 use solana_program::instruction::Instruction;
 use solana_program::program_pack::Pack;
 use solana_program::pubkey::Pubkey;
-use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
 use solana_sdk::account::Account;
 use solana_sdk::signature::Signer;
 use solana_sdk::{signature::Keypair, transaction::Transaction};
+use spl_name_service::state::NameRecordHeader;
 use spl_token::state::Mint;
 
 // Utils
@@ -57,3 +61,67 @@ pub fn mint_bootstrap(
     );
     (address, mint_info)
 }
+
+/// Creates a program test with the SNS Records and name service programs
+pub fn new_program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::new(
+        "sns_records",
+        sns_records::ID,
+        processor!(process_instruction),
+    );
+
+    program_test.add_program("spl_name_service", spl_name_service::ID, None);
+
+    program_test
+}
+
+/// Creates a program test with the SNS Records and name service programs, a funded `owner` and a
+/// `domain` owned by `owner`
+pub fn program_test_with_domain(owner: &Pubkey, domain: &Pubkey) -> ProgramTest {
+    let mut program_test = new_program_test();
+
+    program_test.add_account(
+        *owner,
+        Account {
+            lamports: 100_000_000_000,
+            ..Account::default()
+        },
+    );
+
+    add_domain(&mut program_test, owner, domain);
+
+    program_test
+}
+
+/// Adds a `domain` owned by `owner` to the program test
+pub fn add_domain(program_test: &mut ProgramTest, owner: &Pubkey, domain: &Pubkey) {
+    let domain_record_header = NameRecordHeader {
+        parent_name: Pubkey::from_str("4kG2PyqixXVUb2CEeNt1ZcVUEoomNssMe8C4hf4Dguch").unwrap(),
+        owner: *owner,
+        class: Pubkey::default(),
+    };
+    program_test.add_account(
+        *domain,
+        Account {
+            lamports: 100_000_000_000,
+            data: domain_record_header.try_to_vec().unwrap(),
+            owner: spl_name_service::ID,
+            ..Account::default()
+        },
+    );
+}
+
+/// Adds a cross-chain domain `x_domain.name_account` owned by `owner`, a mocked account of the
+/// SNS Warp emitter holding the `XDomain` state
+pub fn add_xchain_domain(program_test: &mut ProgramTest, owner: &Pubkey, x_domain: &XDomain) {
+    program_test.add_account(
+        *owner,
+        Account {
+            lamports: 1_000_000_000,
+            data: x_domain.try_to_vec().unwrap(),
+            owner: sns_warp_common::constants::EMITTER_KEY,
+            ..Account::default()
+        },
+    );
+    add_domain(program_test, owner, &x_domain.name_account);
+}
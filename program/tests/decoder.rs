@@ -0,0 +1,384 @@
+use sns_records::instruction::{
+    allocate_and_post_record,
+    batch_records::{self, Operation},
+    decode, edit_record, migrate_record, patch_record,
+    validate_ethereum_signature::{self, EthereumMessageFormat},
+    validate_solana_signature, write_roa, DecodedInstruction, ProgramInstruction,
+};
+use sns_records::record::RecordName;
+use sns_records::state::validation::Validation;
+use solana_program::{
+    instruction::Instruction, program_error::ProgramError, pubkey::Pubkey, system_program,
+};
+
+fn account_keys(instruction: &Instruction) -> Vec<Pubkey> {
+    instruction
+        .accounts
+        .iter()
+        .map(|meta| meta.pubkey)
+        .collect()
+}
+
+fn roundtrip(instruction: Instruction) -> &'static str {
+    let keys = account_keys(&instruction);
+    let decoded = decode(&instruction.program_id, &keys, &instruction.data).unwrap();
+    let name = decoded.name();
+    assert_eq!(decoded.into_instruction(), instruction);
+    name
+}
+
+#[test]
+fn test_decode_roundtrip() {
+    let fee_payer = Pubkey::new_unique();
+    let record = Pubkey::new_unique();
+    let domain = Pubkey::new_unique();
+    let domain_owner = Pubkey::new_unique();
+
+    let edit = sns_records::instruction::edit_record(
+        edit_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &fee_payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &domain_owner,
+            central_state: &sns_records::central_state::KEY,
+        },
+        edit_record::Params {
+            record: "TXT".to_owned(),
+            content: b"hello world".to_vec(),
+        },
+    );
+    let keys = account_keys(&edit);
+    match decode(&edit.program_id, &keys, &edit.data).unwrap() {
+        DecodedInstruction::EditRecord { accounts, params } => {
+            assert_eq!(accounts.record, &record);
+            assert_eq!(accounts.domain, &domain);
+            assert_eq!(accounts.domain_owner, &domain_owner);
+            assert_eq!(params.record, "TXT");
+            assert_eq!(params.content, b"hello world");
+        }
+        decoded => panic!("Unexpected instruction {}", decoded.name()),
+    }
+    assert_eq!(roundtrip(edit.clone()), "EditRecord");
+
+    assert_eq!(
+        roundtrip(sns_records::instruction::validate_solana_signature(
+            validate_solana_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &fee_payer,
+                record: &record,
+                domain: &domain,
+                domain_owner: &domain_owner,
+                central_state: &sns_records::central_state::KEY,
+                verifier: &fee_payer,
+            },
+            validate_solana_signature::Params { staleness: true },
+        )),
+        "ValidateSolanaSignature"
+    );
+
+    assert_eq!(
+        roundtrip(sns_records::instruction::patch_record(
+            patch_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &fee_payer,
+                record: &record,
+                domain: &domain,
+                domain_owner: &domain_owner,
+                central_state: &sns_records::central_state::KEY,
+            },
+            patch_record::Params {
+                patches: vec![patch_record::Patch {
+                    offset: 3,
+                    data: b"abc".to_vec(),
+                }],
+            },
+        )),
+        "PatchRecord"
+    );
+
+    assert_eq!(
+        roundtrip(sns_records::instruction::migrate_record(
+            migrate_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &fee_payer,
+                record: &record,
+                domain: &domain,
+                domain_owner: &domain_owner,
+                central_state: &sns_records::central_state::KEY,
+            },
+            migrate_record::Params {},
+        )),
+        "MigrateRecord"
+    );
+
+    ////
+    // Batches keep the trailing record accounts
+    ////
+    let records = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let batch = sns_records::instruction::batch_records(
+        batch_records::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &fee_payer,
+            domain: &domain,
+            domain_owner: &domain_owner,
+            central_state: &sns_records::central_state::KEY,
+            records: &records,
+        },
+        batch_records::Params {
+            operations: vec![
+                Operation::Edit {
+                    record: "TXT".to_owned(),
+                    content: b"hello".to_vec(),
+                    validate_content: false,
+                },
+                Operation::Delete {
+                    record: "url".to_owned(),
+                },
+            ],
+        },
+    );
+    let batch_keys = account_keys(&batch);
+    match decode(&batch.program_id, &batch_keys, &batch.data).unwrap() {
+        DecodedInstruction::BatchRecords { accounts, params } => {
+            assert_eq!(accounts.records, &records);
+            assert_eq!(params.operations.len(), 2);
+            assert_eq!(params.operations[1].record(), "url");
+        }
+        decoded => panic!("Unexpected instruction {}", decoded.name()),
+    }
+    assert_eq!(roundtrip(batch), "BatchRecords");
+
+    ////
+    // Invalid instructions
+    ////
+    assert!(matches!(
+        decode(&Pubkey::new_unique(), &keys, &edit.data),
+        Err(ProgramError::IncorrectProgramId)
+    ));
+    assert!(matches!(
+        decode(&edit.program_id, &keys[..6], &edit.data),
+        Err(ProgramError::NotEnoughAccountKeys)
+    ));
+    assert!(matches!(
+        decode(&edit.program_id, &keys, &[]),
+        Err(ProgramError::InvalidInstructionData)
+    ));
+    assert!(matches!(
+        decode(&edit.program_id, &keys, &[u8::MAX]),
+        Err(ProgramError::InvalidInstructionData)
+    ));
+    assert!(decode(&edit.program_id, &keys, &edit.data[..edit.data.len() - 1]).is_err());
+}
+
+#[test]
+fn test_decode_versioned_params() {
+    let fee_payer = Pubkey::new_unique();
+    let record = Pubkey::new_unique();
+    let domain = Pubkey::new_unique();
+
+    ////
+    // The parameters of the original instructions keep their layout
+    ////
+    let accounts = validate_ethereum_signature::Accounts {
+        system_program: &system_program::ID,
+        spl_name_service_program: &spl_name_service::ID,
+        fee_payer: &fee_payer,
+        record: &record,
+        domain: &domain,
+        domain_owner: &fee_payer,
+        central_state: &sns_records::central_state::KEY,
+    };
+    let ix = sns_records::instruction::validate_ethereum_signature(
+        accounts,
+        validate_ethereum_signature::Params {
+            validation: Validation::Ethereum,
+            signature: vec![1; 65],
+            expected_pubkey: vec![2; 20],
+        },
+    );
+    assert_eq!(
+        ix.data,
+        [
+            &[
+                ProgramInstruction::ValidateEthereumSignature as u8,
+                Validation::Ethereum as u8,
+            ][..],
+            &65u32.to_le_bytes(),
+            &[1; 65],
+            &20u32.to_le_bytes(),
+            &[2; 20],
+        ]
+        .concat()
+    );
+    assert_eq!(roundtrip(ix), "ValidateEthereumSignature");
+
+    let accounts = validate_ethereum_signature::Accounts {
+        system_program: &system_program::ID,
+        spl_name_service_program: &spl_name_service::ID,
+        fee_payer: &fee_payer,
+        record: &record,
+        domain: &domain,
+        domain_owner: &fee_payer,
+        central_state: &sns_records::central_state::KEY,
+    };
+    assert_eq!(
+        roundtrip(sns_records::instruction::validate_ethereum_signature_v2(
+            accounts,
+            validate_ethereum_signature::ParamsV2 {
+                validation: Validation::Ethereum,
+                signature: vec![1; 65],
+                expected_pubkey: vec![2; 20],
+                format: EthereumMessageFormat::Eip712,
+            },
+        )),
+        "ValidateEthereumSignatureV2"
+    );
+
+    let accounts = write_roa::Accounts {
+        system_program: &system_program::ID,
+        spl_name_service_program: &spl_name_service::ID,
+        fee_payer: &fee_payer,
+        record: &record,
+        domain: &domain,
+        domain_owner: &fee_payer,
+        central_state: &sns_records::central_state::KEY,
+    };
+    let ix = sns_records::instruction::write_roa(
+        accounts,
+        write_roa::Params {
+            roa_id: vec![3; 32],
+        },
+    );
+    assert_eq!(
+        ix.data,
+        [
+            &[ProgramInstruction::WriteRoa as u8][..],
+            &32u32.to_le_bytes(),
+            &[3; 32],
+        ]
+        .concat()
+    );
+    assert_eq!(roundtrip(ix), "WriteRoa");
+
+    let accounts = write_roa::Accounts {
+        system_program: &system_program::ID,
+        spl_name_service_program: &spl_name_service::ID,
+        fee_payer: &fee_payer,
+        record: &record,
+        domain: &domain,
+        domain_owner: &fee_payer,
+        central_state: &sns_records::central_state::KEY,
+    };
+    assert_eq!(
+        roundtrip(sns_records::instruction::write_roa_v2(
+            accounts,
+            write_roa::ParamsV2 {
+                validation: Validation::UnverifiedEthereum,
+                roa_id: vec![3; 20],
+            },
+        )),
+        "WriteRoaV2"
+    );
+
+    let accounts = edit_record::Accounts {
+        system_program: &system_program::ID,
+        spl_name_service_program: &spl_name_service::ID,
+        fee_payer: &fee_payer,
+        record: &record,
+        domain: &domain,
+        domain_owner: &fee_payer,
+        central_state: &sns_records::central_state::KEY,
+    };
+    let ix = sns_records::instruction::edit_record(
+        accounts,
+        edit_record::Params {
+            record: "SOL".to_owned(),
+            content: vec![4; 32],
+        },
+    );
+    assert_eq!(
+        ix.data,
+        [
+            &[ProgramInstruction::EditRecord as u8][..],
+            &3u32.to_le_bytes(),
+            b"SOL",
+            &32u32.to_le_bytes(),
+            &[4; 32],
+        ]
+        .concat()
+    );
+    assert_eq!(roundtrip(ix), "EditRecord");
+
+    let accounts = edit_record::Accounts {
+        system_program: &system_program::ID,
+        spl_name_service_program: &spl_name_service::ID,
+        fee_payer: &fee_payer,
+        record: &record,
+        domain: &domain,
+        domain_owner: &fee_payer,
+        central_state: &sns_records::central_state::KEY,
+    };
+    assert_eq!(
+        roundtrip(sns_records::instruction::edit_record_v2(
+            accounts,
+            edit_record::ParamsV2 {
+                record: "SOL".to_owned(),
+                content: vec![4; 32],
+                validate_content: true,
+            },
+        )),
+        "EditRecordV2"
+    );
+
+    let accounts = allocate_and_post_record::Accounts {
+        system_program: &system_program::ID,
+        spl_name_service_program: &spl_name_service::ID,
+        fee_payer: &fee_payer,
+        record: &record,
+        domain: &domain,
+        domain_owner: &fee_payer,
+        central_state: &sns_records::central_state::KEY,
+    };
+    let ix = sns_records::instruction::allocate_and_post_record(
+        accounts,
+        allocate_and_post_record::Params {
+            record: "SOL".to_owned(),
+            content: vec![4; 32],
+        },
+    );
+    assert_eq!(
+        ix.data,
+        [
+            &[ProgramInstruction::AllocateAndPostRecord as u8][..],
+            &3u32.to_le_bytes(),
+            b"SOL",
+            &32u32.to_le_bytes(),
+            &[4; 32],
+        ]
+        .concat()
+    );
+    assert_eq!(roundtrip(ix), "AllocateAndPostRecord");
+
+    let accounts = allocate_and_post_record::Accounts {
+        system_program: &system_program::ID,
+        spl_name_service_program: &spl_name_service::ID,
+        fee_payer: &fee_payer,
+        record: &record,
+        domain: &domain,
+        domain_owner: &fee_payer,
+        central_state: &sns_records::central_state::KEY,
+    };
+    assert_eq!(
+        roundtrip(sns_records::instruction::allocate_and_post_record_v2(
+            accounts,
+            allocate_and_post_record::ParamsV2::new(RecordName::Sol, vec![4; 32]),
+        )),
+        "AllocateAndPostRecordV2"
+    );
+}
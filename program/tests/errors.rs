@@ -136,7 +136,8 @@ async fn test_functional() {
     ////
     // Only domain owner can allocate and post record
     ////
-    let content = "some random content".as_bytes();
+    // The SOL record posts the address of the guardian, who later verifies its RoA
+    let content = guardian.pubkey().to_bytes();
     let ix = sns_records::instruction::allocate_and_post_record(
         sns_records::instruction::allocate_and_post_record::Accounts {
             system_program: &system_program::ID,
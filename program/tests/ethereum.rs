@@ -0,0 +1,154 @@
+use {
+    serde_json::Value,
+    sns_records::{
+        error::SnsRecordsError,
+        instruction::validate_ethereum_signature::{
+            eth_address, message_for_format, normalize_signature, sign_message, sign_record,
+            EthereumMessageFormat, EthereumVerifier,
+        },
+        verifier::SignatureVerifier,
+    },
+    solana_program::pubkey::Pubkey,
+    std::{convert::TryInto, str::FromStr},
+};
+
+const VECTORS: &str = include_str!("vectors/ethereum.json");
+
+fn field<'a>(vector: &'a Value, name: &str) -> &'a str {
+    vector[name].as_str().unwrap()
+}
+
+fn parse_secret_key(vector: &Value) -> libsecp256k1::SecretKey {
+    let secret_key: [u8; 32] = hex::decode(field(vector, "secretKey"))
+        .unwrap()
+        .try_into()
+        .unwrap();
+    libsecp256k1::SecretKey::parse(&secret_key).unwrap()
+}
+
+#[test]
+fn test_ethereum_vectors() {
+    let vectors: Value = serde_json::from_str(VECTORS).unwrap();
+
+    ////
+    // Signing helper
+    ////
+    let vector = &vectors["signing"];
+    let secret_key = parse_secret_key(vector);
+    let message = hex::decode(field(vector, "message")).unwrap();
+    assert_eq!(
+        hex::encode(eth_address(&libsecp256k1::PublicKey::from_secret_key(
+            &secret_key
+        ))),
+        field(vector, "address")
+    );
+    assert_eq!(
+        hex::encode(sign_message(&secret_key, &message)),
+        field(vector, "signature")
+    );
+
+    ////
+    // Record messages
+    ////
+    for vector in vectors["records"].as_array().unwrap() {
+        let secret_key = parse_secret_key(vector);
+        let address = eth_address(&libsecp256k1::PublicKey::from_secret_key(&secret_key));
+        let format = match field(vector, "format") {
+            "Eip191" => EthereumMessageFormat::Eip191,
+            "Eip712" => EthereumMessageFormat::Eip712,
+            format => panic!("Unknown format {}", format),
+        };
+        let content = field(vector, "content").as_bytes();
+        let record_key = Pubkey::from_str(field(vector, "recordKey")).unwrap();
+        let staleness_id = Pubkey::from_str(field(vector, "stalenessId")).unwrap();
+
+        let message = message_for_format(format, content, &record_key, &staleness_id);
+        let signature = sign_record(&secret_key, format, content, &record_key, &staleness_id);
+        assert_eq!(hex::encode(address), field(vector, "address"));
+        assert_eq!(hex::encode(&message), field(vector, "message"));
+        assert_eq!(hex::encode(signature), field(vector, "signature"));
+
+        EthereumVerifier
+            .verify(&message, &signature, &address)
+            .unwrap();
+        assert!(EthereumVerifier
+            .verify(&message, &signature, &[0; 20])
+            .is_err());
+    }
+}
+
+/// Order of the secp256k1 curve
+const SECP256K1_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+
+/// Big endian `a - b`, with `a >= b`
+fn sub(a: &[u8; 32], b: &[u8]) -> [u8; 32] {
+    let mut result = [0; 32];
+    let mut borrow = 0;
+    for i in (0..32).rev() {
+        let diff = a[i] as i16 - b[i] as i16 - borrow;
+        borrow = (diff < 0) as i16;
+        result[i] = diff.rem_euclid(256) as u8;
+    }
+    result
+}
+
+#[test]
+fn test_normalize_signature() {
+    let secret_key = libsecp256k1::SecretKey::parse(&[7; 32]).unwrap();
+    let address = eth_address(&libsecp256k1::PublicKey::from_secret_key(&secret_key));
+    let message = message_for_format(
+        EthereumMessageFormat::Eip191,
+        b"hello world",
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+    );
+    let signature = sign_message(&secret_key, &message);
+    let recovery_id = signature[64] - 27;
+
+    // 0/1 recovery ID
+    let raw = [&signature[..64], &[recovery_id]].concat();
+    // EIP-2098 compact signature
+    let mut compact = signature[..64].to_vec();
+    compact[32] |= recovery_id << 7;
+
+    for encoding in [&signature[..], &raw, &compact] {
+        assert_eq!(
+            normalize_signature(encoding).unwrap(),
+            (signature[..64].try_into().unwrap(), recovery_id)
+        );
+        EthereumVerifier
+            .verify(&message, encoding, &address)
+            .unwrap();
+    }
+
+    ////
+    // The malleable counterpart of the signature is rejected
+    ////
+    let high_s = sub(&SECP256K1_ORDER, &signature[32..64]);
+    let malleable = [&signature[..32], &high_s, &[55 - signature[64]]].concat();
+    assert!(matches!(
+        normalize_signature(&malleable),
+        Err(SnsRecordsError::HighSSignature)
+    ));
+
+    ////
+    // Invalid encodings
+    ////
+    assert!(matches!(
+        normalize_signature(&signature[..63]),
+        Err(SnsRecordsError::InvalidSignatureLength)
+    ));
+    assert!(matches!(
+        normalize_signature(&[&signature[..], &[0]].concat()),
+        Err(SnsRecordsError::InvalidSignatureLength)
+    ));
+    let mut invalid_v = signature;
+    invalid_v[64] = 29;
+    assert!(matches!(
+        normalize_signature(&invalid_v),
+        Err(SnsRecordsError::InvalidRecoveryId)
+    ));
+}
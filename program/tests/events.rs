@@ -0,0 +1,65 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use borsh::BorshSerialize;
+use sns_records::{
+    events::{decode_logs, RecordEvent, RecordEventKind, EVENT_TAG},
+    state::validation::Validation,
+};
+use solana_program::{hash::hash, pubkey::Pubkey};
+
+fn data_log(fields: &[&[u8]]) -> String {
+    let fields = fields
+        .iter()
+        .map(|field| STANDARD.encode(field))
+        .collect::<Vec<_>>();
+    format!("Program data: {}", fields.join(" "))
+}
+
+#[test]
+fn test_decode_logs() {
+    let record = Pubkey::new_unique();
+    let domain = Pubkey::new_unique();
+    let allocated = RecordEvent {
+        kind: RecordEventKind::Allocated,
+        record,
+        domain,
+        record_name: Some("TXT".to_owned()),
+        staleness_validation: Validation::None,
+        right_of_association_validation: Validation::None,
+        content_hash: hash(b"hello world").to_bytes(),
+    };
+    let validated = RecordEvent {
+        kind: RecordEventKind::StalenessValidated,
+        record_name: None,
+        staleness_validation: Validation::Solana,
+        ..allocated.clone()
+    };
+    let allocated_log = data_log(&[&EVENT_TAG, &allocated.try_to_vec().unwrap()]);
+    let validated_log = data_log(&[&EVENT_TAG, &validated.try_to_vec().unwrap()]);
+    let program = sns_records::ID.to_string();
+    let other_program = Pubkey::new_unique().to_string();
+
+    let logs = vec![
+        format!("Program {} invoke [1]", program),
+        "Program log: [+] Instruction: Allocate and post record".to_owned(),
+        format!("Program {} invoke [2]", spl_name_service::ID),
+        // Data logged by an invoked program
+        allocated_log.clone(),
+        format!("Program {} success", spl_name_service::ID),
+        allocated_log.clone(),
+        // Data which is not an event
+        data_log(&[b"other"]),
+        format!("Program {} consumed 1000 of 200000 compute units", program),
+        format!("Program {} success", program),
+        // Events from another program
+        format!("Program {} invoke [1]", other_program),
+        allocated_log,
+        format!("Program {} success", other_program),
+        format!("Program {} invoke [1]", program),
+        "Program data: not base64".to_owned(),
+        validated_log,
+        format!("Program {} success", program),
+    ];
+
+    assert_eq!(decode_logs(&logs), vec![allocated, validated]);
+    assert!(decode_logs::<&str>(&[]).is_empty());
+}
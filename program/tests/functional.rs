@@ -2,11 +2,12 @@ use std::str::FromStr;
 
 use sns_records::{
     entrypoint::process_instruction,
-    instruction::validate_solana_signature,
+    instruction::{validate_ethereum_signature::EthereumMessageFormat, validate_solana_signature},
+    record::MAX_TEXT_LENGTH,
     state::{record_header::RecordHeader, validation::Validation},
     utils::get_record_key_and_seeds,
 };
-use solana_program::{program_pack::Pack, system_program};
+use solana_program::{entrypoint::MAX_PERMITTED_DATA_INCREASE, program_pack::Pack, system_program};
 
 use {
     borsh::ser::BorshSerialize,
@@ -24,6 +25,7 @@ pub mod common;
 #[tokio::test]
 async fn test_functional() {
     use common::utils::sign_send_instructions;
+    use sns_records::state::record_view::RecordView;
     // Create program and test environment
 
     // Used for verifying the SOL RoA
@@ -126,7 +128,7 @@ async fn test_functional() {
         .await
         .unwrap()
         .unwrap();
-    let record_hd = RecordHeader::from_buffer(&account.data);
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
     assert_eq!(record_hd.content_length, 0);
     assert_eq!(
         record_hd.right_of_association_validation,
@@ -203,7 +205,7 @@ async fn test_functional() {
         .await
         .unwrap()
         .unwrap();
-    let record_hd = RecordHeader::from_buffer(&account.data);
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
     assert_eq!(record_hd.content_length, content.len() as u32);
     assert_eq!(
         record_hd.right_of_association_validation,
@@ -248,7 +250,7 @@ async fn test_functional() {
         .await
         .unwrap()
         .unwrap();
-    let record_hd = RecordHeader::from_buffer(&account.data);
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
     assert_eq!(record_hd.content_length, content.len() as u32);
     assert_eq!(
         record_hd.right_of_association_validation,
@@ -293,7 +295,7 @@ async fn test_functional() {
         .await
         .unwrap()
         .unwrap();
-    let record_hd = RecordHeader::from_buffer(&account.data);
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
     assert_eq!(record_hd.content_length, content.len() as u32);
     assert_eq!(
         record_hd.right_of_association_validation,
@@ -334,7 +336,7 @@ async fn test_functional() {
         .await
         .unwrap()
         .unwrap();
-    let record_hd = RecordHeader::from_buffer(&account.data);
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
     assert_eq!(record_hd.content_length, content.len() as u32);
     assert_eq!(
         record_hd.right_of_association_validation,
@@ -375,7 +377,7 @@ async fn test_functional() {
         .await
         .unwrap()
         .unwrap();
-    let record_hd = RecordHeader::from_buffer(&account.data);
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
     assert_eq!(record_hd.content_length, content.len() as u32);
     assert_eq!(
         record_hd.right_of_association_validation,
@@ -415,7 +417,7 @@ async fn test_functional() {
         .await
         .unwrap()
         .unwrap();
-    let record_hd = RecordHeader::from_buffer(&account.data);
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
     assert_eq!(record_hd.content_length, content.len() as u32);
     assert_eq!(
         record_hd.right_of_association_validation,
@@ -454,7 +456,7 @@ async fn test_functional() {
         .await
         .unwrap()
         .unwrap();
-    let record_hd = RecordHeader::from_buffer(&account.data);
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
     assert_eq!(record_hd.content_length, content.len() as u32);
     assert_eq!(
         record_hd.right_of_association_validation,
@@ -498,7 +500,7 @@ async fn test_functional() {
         .await
         .unwrap()
         .unwrap();
-    let record_hd = RecordHeader::from_buffer(&account.data);
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
     assert_eq!(record_hd.content_length, content.len() as u32);
     assert_eq!(
         record_hd.right_of_association_validation,
@@ -535,7 +537,7 @@ async fn test_functional() {
         .await
         .unwrap()
         .unwrap();
-    let record_hd = RecordHeader::from_buffer(&account.data);
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
     assert_eq!(record_hd.content_length, content.len() as u32);
     assert_eq!(
         record_hd.right_of_association_validation,
@@ -583,7 +585,7 @@ async fn test_functional() {
         .await
         .unwrap()
         .unwrap();
-    let record_hd = RecordHeader::from_buffer(&account.data);
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
     assert_eq!(record_hd.content_length, content.len() as u32);
     assert_eq!(
         record_hd.right_of_association_validation,
@@ -639,15 +641,3479 @@ async fn test_functional() {
         .await
         .unwrap()
         .unwrap();
-    let record_hd = RecordHeader::from_buffer(&account.data);
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
     assert_eq!(record_hd.content_length, content.len() as u32);
     assert_eq!(
         record_hd.right_of_association_validation,
         Validation::None as u16
     );
     assert_eq!(record_hd.staleness_validation, Validation::Solana as u16);
+    // The cleared RoA ID is removed and the content is unchanged
     assert_eq!(
         &account.data[NameRecordHeader::LEN + RecordHeader::LEN..],
-        [bob.pubkey().as_ref(), &expected_pubkey, &content].concat()
+        [bob.pubkey().as_ref(), &content].concat()
     );
+    let record = RecordView::new(&account.data).unwrap();
+    assert!(record.roa_id().is_empty());
+    assert_eq!(record.content(), content);
+}
+
+#[tokio::test]
+async fn test_chunked_upload() {
+    use common::utils::{program_test_with_domain, sign_send_instructions};
+    use sns_records::state::record_header::{RecordHeaderV2, VersionedRecordHeader};
+
+    let alice = Keypair::new();
+    let domain = Keypair::new().pubkey();
+
+    let program_test = program_test_with_domain(&alice.pubkey(), &domain);
+    let mut prg_test_ctx = program_test.start_with_context().await;
+
+    let record = "pic";
+    let (record_key, _) = get_record_key_and_seeds(&domain, record);
+    let chunk_size = 900;
+
+    // The last content is larger than the reallocation limit of a single instruction
+    for content in [
+        (0..3_000).map(|i| i as u8).collect::<Vec<_>>(),
+        (0..4_500).map(|i| (i * 7) as u8).collect::<Vec<_>>(),
+        (0..25_000).map(|i| (i * 3) as u8).collect::<Vec<_>>(),
+    ] {
+        ////
+        // Begin upload (allocates the record the first time, truncates it the second time)
+        ////
+        let ix = sns_records::instruction::begin_upload(
+            sns_records::instruction::begin_upload::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::begin_upload::Params {
+                record: record.to_owned(),
+                content_length: content.len() as u32,
+            },
+        );
+        sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice])
+            .await
+            .unwrap();
+        let account = prg_test_ctx
+            .banks_client
+            .get_account(record_key)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(VersionedRecordHeader::try_from_buffer(&account.data)
+            .unwrap()
+            .is_pending_upload());
+
+        let write_ix = |offset: usize, data: &[u8]| {
+            sns_records::instruction::write_chunk(
+                sns_records::instruction::write_chunk::Accounts {
+                    system_program: &system_program::ID,
+                    spl_name_service_program: &spl_name_service::ID,
+                    fee_payer: &alice.pubkey(),
+                    record: &record_key,
+                    domain: &domain,
+                    domain_owner: &alice.pubkey(),
+                    central_state: &sns_records::central_state::KEY,
+                },
+                sns_records::instruction::write_chunk::Params {
+                    offset: offset as u32,
+                    data: data.to_vec(),
+                },
+            )
+        };
+
+        ////
+        // Chunks cannot grow the record beyond the reallocation limit
+        ////
+        let res = sign_send_instructions(
+            &mut prg_test_ctx,
+            vec![write_ix(MAX_PERMITTED_DATA_INCREASE, &[1])],
+            vec![&alice],
+        )
+        .await;
+        assert!(res.is_err());
+
+        ////
+        // Write chunks out of order, the first one last
+        ////
+        let chunks = content.chunks(chunk_size).enumerate().collect::<Vec<_>>();
+        for (i, chunk) in chunks[1..].iter().chain(&chunks[..1]) {
+            sign_send_instructions(
+                &mut prg_test_ctx,
+                vec![write_ix(i * chunk_size, chunk)],
+                vec![&alice],
+            )
+            .await
+            .unwrap();
+        }
+
+        ////
+        // Finalize with a wrong hash
+        ////
+        let finalize_ix = |content_hash: [u8; 32]| {
+            sns_records::instruction::finalize_upload(
+                sns_records::instruction::finalize_upload::Accounts {
+                    system_program: &system_program::ID,
+                    spl_name_service_program: &spl_name_service::ID,
+                    fee_payer: &alice.pubkey(),
+                    record: &record_key,
+                    domain: &domain,
+                    domain_owner: &alice.pubkey(),
+                    central_state: &sns_records::central_state::KEY,
+                },
+                sns_records::instruction::finalize_upload::Params {
+                    content_length: content.len() as u32,
+                    content_hash,
+                },
+            )
+        };
+        let res =
+            sign_send_instructions(&mut prg_test_ctx, vec![finalize_ix([0; 32])], vec![&alice])
+                .await;
+        assert!(res.is_err());
+
+        ////
+        // Finalize
+        ////
+        let content_hash = solana_program::hash::hash(&content).to_bytes();
+        sign_send_instructions(
+            &mut prg_test_ctx,
+            vec![finalize_ix(content_hash)],
+            vec![&alice],
+        )
+        .await
+        .unwrap();
+
+        ////
+        // State verification
+        ////
+        let account = prg_test_ctx
+            .banks_client
+            .get_account(record_key)
+            .await
+            .unwrap()
+            .unwrap();
+        // Uploads use the V2 header, whose pending flag is cleared once finalized
+        let record_hd = match VersionedRecordHeader::try_from_buffer(&account.data).unwrap() {
+            VersionedRecordHeader::V2(header) => header,
+            header => panic!("Unexpected header {:?}", header),
+        };
+        assert!(!VersionedRecordHeader::V2(record_hd).is_pending_upload());
+        assert_eq!(record_hd.flags, 0);
+        assert_eq!(record_hd.content_length, content.len() as u32);
+        assert_eq!(record_hd.staleness_validation, Validation::None as u16);
+        assert_eq!(
+            record_hd.right_of_association_validation,
+            Validation::None as u16
+        );
+        assert_eq!(
+            &account.data[NameRecordHeader::LEN + RecordHeaderV2::LEN..],
+            content.as_slice()
+        );
+
+        ////
+        // Finalized records cannot be written to
+        ////
+        let ix = sns_records::instruction::write_chunk(
+            sns_records::instruction::write_chunk::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::write_chunk::Params {
+                offset: 0,
+                data: vec![1],
+            },
+        );
+        let res = sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice]).await;
+        assert!(res.is_err());
+    }
+
+    ////
+    // The content must fit in an account
+    ////
+    let (record_key, _) = get_record_key_and_seeds(&domain, "url");
+    let ix = sns_records::instruction::begin_upload(
+        sns_records::instruction::begin_upload::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &alice.pubkey(),
+            record: &record_key,
+            domain: &domain,
+            domain_owner: &alice.pubkey(),
+            central_state: &sns_records::central_state::KEY,
+        },
+        sns_records::instruction::begin_upload::Params {
+            record: "url".to_owned(),
+            content_length: u32::MAX,
+        },
+    );
+    let res = sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice]).await;
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn test_patch_record() {
+    use common::utils::{program_test_with_domain, sign_send_instructions};
+
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let domain = Keypair::new().pubkey();
+
+    let program_test = program_test_with_domain(&alice.pubkey(), &domain);
+    let mut prg_test_ctx = program_test.start_with_context().await;
+
+    let record = "TXT";
+    let (record_key, _) = get_record_key_and_seeds(&domain, record);
+    let content = "hello world".as_bytes();
+
+    ////
+    // Post a record with a staleness and a verified RoA
+    ////
+    let ixs = vec![
+        sns_records::instruction::allocate_and_post_record(
+            sns_records::instruction::allocate_and_post_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::allocate_and_post_record::Params {
+                content: content.to_vec(),
+                record: record.to_owned(),
+            },
+        ),
+        validate_solana_signature(
+            validate_solana_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+                verifier: &alice.pubkey(),
+            },
+            validate_solana_signature::Params { staleness: true },
+        ),
+        sns_records::instruction::write_roa(
+            sns_records::instruction::write_roa::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::write_roa::Params {
+                roa_id: bob.pubkey().to_bytes().to_vec(),
+            },
+        ),
+        validate_solana_signature(
+            validate_solana_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+                verifier: &bob.pubkey(),
+            },
+            validate_solana_signature::Params { staleness: false },
+        ),
+    ];
+    sign_send_instructions(&mut prg_test_ctx, ixs, vec![&alice, &bob])
+        .await
+        .unwrap();
+
+    let patch_ix = |patches| {
+        sns_records::instruction::patch_record(
+            sns_records::instruction::patch_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::patch_record::Params { patches },
+        )
+    };
+
+    ////
+    // Patch the content
+    ////
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![patch_ix(vec![
+            sns_records::instruction::patch_record::Patch {
+                offset: 0,
+                data: b"H".to_vec(),
+            },
+            sns_records::instruction::patch_record::Patch {
+                offset: 6,
+                data: b"WORLD".to_vec(),
+            },
+        ])],
+        vec![&alice],
+    )
+    .await
+    .unwrap();
+
+    ////
+    // State verification: both the staleness and the RoA are kept
+    ////
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(record_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
+    assert_eq!(record_hd.content_length, content.len() as u32);
+    assert_eq!(record_hd.staleness_validation, Validation::Solana as u16);
+    assert_eq!(
+        record_hd.right_of_association_validation,
+        Validation::Solana as u16
+    );
+    assert_eq!(
+        &account.data[NameRecordHeader::LEN + RecordHeader::LEN..],
+        [
+            alice.pubkey().as_ref(),
+            bob.pubkey().as_ref(),
+            "Hello WORLD".as_bytes()
+        ]
+        .concat()
+    );
+
+    ////
+    // Patches cannot go past the content
+    ////
+    let res = sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![patch_ix(vec![
+            sns_records::instruction::patch_record::Patch {
+                offset: content.len() as u32 - 1,
+                data: b"!!".to_vec(),
+            },
+        ])],
+        vec![&alice],
+    )
+    .await;
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn test_edit_record_preserve_staleness() {
+    use common::utils::{program_test_with_domain, sign_send_instructions};
+
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let domain = Keypair::new().pubkey();
+
+    let mut program_test = program_test_with_domain(&alice.pubkey(), &domain);
+    program_test.add_account(
+        bob.pubkey(),
+        Account {
+            lamports: 100_000_000_000,
+            ..Account::default()
+        },
+    );
+    let mut prg_test_ctx = program_test.start_with_context().await;
+
+    let record = "url";
+    let (record_key, _) = get_record_key_and_seeds(&domain, record);
+
+    ////
+    // Post a record with a staleness and a verified RoA
+    ////
+    let ixs = vec![
+        sns_records::instruction::allocate_and_post_record(
+            sns_records::instruction::allocate_and_post_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::allocate_and_post_record::Params {
+                content: "https://bonfida.org".as_bytes().to_vec(),
+                record: record.to_owned(),
+            },
+        ),
+        validate_solana_signature(
+            validate_solana_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+                verifier: &alice.pubkey(),
+            },
+            validate_solana_signature::Params { staleness: true },
+        ),
+        sns_records::instruction::write_roa(
+            sns_records::instruction::write_roa::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::write_roa::Params {
+                roa_id: alice.pubkey().to_bytes().to_vec(),
+            },
+        ),
+        validate_solana_signature(
+            validate_solana_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+                verifier: &alice.pubkey(),
+            },
+            validate_solana_signature::Params { staleness: false },
+        ),
+    ];
+    sign_send_instructions(&mut prg_test_ctx, ixs, vec![&alice])
+        .await
+        .unwrap();
+
+    ////
+    // Alice edits the record, the staleness is kept and the RoA cleared
+    ////
+    let content = "https://sns.id".as_bytes();
+    let ix = sns_records::instruction::edit_record_preserve_staleness(
+        sns_records::instruction::edit_record_preserve_staleness::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &alice.pubkey(),
+            record: &record_key,
+            domain: &domain,
+            domain_owner: &alice.pubkey(),
+            central_state: &sns_records::central_state::KEY,
+        },
+        sns_records::instruction::edit_record_preserve_staleness::Params {
+            content: content.to_vec(),
+            record: record.to_owned(),
+        },
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice])
+        .await
+        .unwrap();
+
+    ////
+    // State verification
+    ////
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(record_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
+    assert_eq!(record_hd.content_length, content.len() as u32);
+    assert_eq!(record_hd.staleness_validation, Validation::Solana as u16);
+    assert_eq!(
+        record_hd.right_of_association_validation,
+        Validation::None as u16
+    );
+    assert_eq!(
+        &account.data[NameRecordHeader::LEN + RecordHeader::LEN..],
+        [alice.pubkey().as_ref(), content].concat()
+    );
+
+    ////
+    // Alice transfers to Bob, Bob's edit drops Alice's staleness
+    ////
+    let content = "https://bonfida.com".as_bytes();
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![
+            spl_name_service::instruction::transfer(
+                spl_name_service::ID,
+                bob.pubkey(),
+                domain,
+                alice.pubkey(),
+                None,
+            )
+            .unwrap(),
+            sns_records::instruction::edit_record_preserve_staleness(
+                sns_records::instruction::edit_record_preserve_staleness::Accounts {
+                    system_program: &system_program::ID,
+                    spl_name_service_program: &spl_name_service::ID,
+                    fee_payer: &bob.pubkey(),
+                    record: &record_key,
+                    domain: &domain,
+                    domain_owner: &bob.pubkey(),
+                    central_state: &sns_records::central_state::KEY,
+                },
+                sns_records::instruction::edit_record_preserve_staleness::Params {
+                    content: content.to_vec(),
+                    record: record.to_owned(),
+                },
+            ),
+        ],
+        vec![&alice, &bob],
+    )
+    .await
+    .unwrap();
+
+    ////
+    // State verification
+    ////
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(record_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
+    assert_eq!(record_hd.content_length, content.len() as u32);
+    assert_eq!(record_hd.staleness_validation, Validation::None as u16);
+    assert_eq!(
+        record_hd.right_of_association_validation,
+        Validation::None as u16
+    );
+    assert_eq!(
+        &account.data[NameRecordHeader::LEN + RecordHeader::LEN..],
+        content
+    );
+}
+
+#[tokio::test]
+async fn test_batch_records() {
+    use common::utils::{program_test_with_domain, sign_send_instructions};
+    use sns_records::instruction::batch_records::{self, Operation};
+
+    let alice = Keypair::new();
+    let domain = Keypair::new().pubkey();
+
+    let program_test = program_test_with_domain(&alice.pubkey(), &domain);
+    let mut prg_test_ctx = program_test.start_with_context().await;
+
+    let batch_ix = |operations: Vec<Operation>| {
+        let records = operations
+            .iter()
+            .map(|o| get_record_key_and_seeds(&domain, o.record()).0)
+            .collect::<Vec<_>>();
+        sns_records::instruction::batch_records(
+            batch_records::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+                records: &records,
+            },
+            batch_records::Params { operations },
+        )
+    };
+
+    ////
+    // Allocate several records at once
+    ////
+    let ix = batch_ix(vec![
+        Operation::AllocateAndPost {
+            record: "TXT".to_owned(),
+            content: b"some text".to_vec(),
+            validate_content: true,
+        },
+        Operation::AllocateAndPost {
+            record: "url".to_owned(),
+            content: b"https://bonfida.org".to_vec(),
+            validate_content: true,
+        },
+    ]);
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice])
+        .await
+        .unwrap();
+
+    ////
+    // Edit, delete and allocate in a single instruction
+    ////
+    let ix = batch_ix(vec![
+        Operation::Edit {
+            record: "TXT".to_owned(),
+            content: b"some other text".to_vec(),
+            validate_content: true,
+        },
+        Operation::Delete {
+            record: "url".to_owned(),
+        },
+        Operation::AllocateAndPost {
+            record: "email".to_owned(),
+            content: b"hello@bonfida.org".to_vec(),
+            validate_content: true,
+        },
+    ]);
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice])
+        .await
+        .unwrap();
+
+    ////
+    // State verification
+    ////
+    for (record, content) in [
+        ("TXT", Some("some other text")),
+        ("url", None),
+        ("email", Some("hello@bonfida.org")),
+    ] {
+        let (record_key, _) = get_record_key_and_seeds(&domain, record);
+        let account = prg_test_ctx
+            .banks_client
+            .get_account(record_key)
+            .await
+            .unwrap();
+        match content {
+            Some(content) => {
+                let account = account.unwrap();
+                let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
+                assert_eq!(record_hd.content_length, content.len() as u32);
+                assert_eq!(
+                    &account.data[NameRecordHeader::LEN + RecordHeader::LEN..],
+                    content.as_bytes()
+                );
+            }
+            None => assert!(account.is_none()),
+        }
+    }
+
+    ////
+    // A failing operation reverts the whole batch
+    ////
+    let ix = batch_ix(vec![
+        Operation::AllocateAndPost {
+            record: "discord".to_owned(),
+            content: b"bonfida".to_vec(),
+            validate_content: true,
+        },
+        Operation::Edit {
+            record: "url".to_owned(),
+            content: b"https://sns.id".to_vec(),
+            validate_content: true,
+        },
+    ]);
+    let res = sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice]).await;
+    assert!(res.is_err());
+
+    let ix = batch_ix(vec![
+        Operation::AllocateAndPost {
+            record: "discord".to_owned(),
+            content: b"bonfida".to_vec(),
+            validate_content: true,
+        },
+        Operation::Edit {
+            record: "TXT".to_owned(),
+            content: vec![b'a'; MAX_TEXT_LENGTH + 1],
+            validate_content: true,
+        },
+    ]);
+    let res = sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice]).await;
+    assert!(res.is_err());
+
+    let (record_key, _) = get_record_key_and_seeds(&domain, "discord");
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(record_key)
+        .await
+        .unwrap();
+    assert!(account.is_none());
+}
+
+#[tokio::test]
+async fn test_validate_ed25519_signature() {
+    use common::utils::{program_test_with_domain, sign_send_instructions};
+    use sns_records::instruction::validate_ed25519_signature::{
+        message_to_sign, new_ed25519_instruction,
+    };
+    use std::convert::TryInto;
+
+    let alice = Keypair::new();
+    // Bob only signs offline and never sends a transaction
+    let bob = Keypair::new();
+    let domain = Keypair::new().pubkey();
+
+    let program_test = program_test_with_domain(&alice.pubkey(), &domain);
+    let mut prg_test_ctx = program_test.start_with_context().await;
+
+    let record = "url";
+    let (record_key, _) = get_record_key_and_seeds(&domain, record);
+    let content = "https://bonfida.org".as_bytes();
+
+    ////
+    // Post a record with a staleness and Bob as RoA
+    ////
+    let ixs = vec![
+        sns_records::instruction::allocate_and_post_record(
+            sns_records::instruction::allocate_and_post_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::allocate_and_post_record::Params {
+                content: content.to_vec(),
+                record: record.to_owned(),
+            },
+        ),
+        validate_solana_signature(
+            validate_solana_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+                verifier: &alice.pubkey(),
+            },
+            validate_solana_signature::Params { staleness: true },
+        ),
+        sns_records::instruction::write_roa(
+            sns_records::instruction::write_roa::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::write_roa::Params {
+                roa_id: bob.pubkey().to_bytes().to_vec(),
+            },
+        ),
+    ];
+    sign_send_instructions(&mut prg_test_ctx, ixs, vec![&alice])
+        .await
+        .unwrap();
+
+    let validate_ix = sns_records::instruction::validate_ed25519_signature(
+        sns_records::instruction::validate_ed25519_signature::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &alice.pubkey(),
+            record: &record_key,
+            domain: &domain,
+            central_state: &sns_records::central_state::KEY,
+            instructions_sysvar: &solana_program::sysvar::instructions::ID,
+        },
+        sns_records::instruction::validate_ed25519_signature::Params {},
+    );
+
+    ////
+    // The validation fails without the Ed25519 instruction
+    ////
+    let res =
+        sign_send_instructions(&mut prg_test_ctx, vec![validate_ix.clone()], vec![&alice]).await;
+    assert!(res.is_err());
+
+    ////
+    // A signature over different content is rejected
+    ////
+    let message = message_to_sign(
+        &record_key,
+        Validation::Solana,
+        alice.pubkey().as_ref(),
+        "https://sns.id".as_bytes(),
+    );
+    let signature = bob.sign_message(&message);
+    let ixs = vec![
+        new_ed25519_instruction(
+            &bob.pubkey(),
+            signature.as_ref().try_into().unwrap(),
+            &message,
+        ),
+        validate_ix.clone(),
+    ];
+    let res = sign_send_instructions(&mut prg_test_ctx, ixs, vec![&alice]).await;
+    assert!(res.is_err());
+
+    ////
+    // Alice relays Bob's offline signature
+    ////
+    let message = message_to_sign(
+        &record_key,
+        Validation::Solana,
+        alice.pubkey().as_ref(),
+        content,
+    );
+    let signature = bob.sign_message(&message);
+    let ixs = vec![
+        new_ed25519_instruction(
+            &bob.pubkey(),
+            signature.as_ref().try_into().unwrap(),
+            &message,
+        ),
+        validate_ix,
+    ];
+    sign_send_instructions(&mut prg_test_ctx, ixs, vec![&alice])
+        .await
+        .unwrap();
+
+    ////
+    // State verification
+    ////
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(record_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
+    assert_eq!(record_hd.staleness_validation, Validation::Solana as u16);
+    assert_eq!(
+        record_hd.right_of_association_validation,
+        Validation::Solana as u16
+    );
+    assert_eq!(
+        &account.data[NameRecordHeader::LEN + RecordHeader::LEN..],
+        [alice.pubkey().as_ref(), bob.pubkey().as_ref(), content].concat()
+    );
+}
+
+#[tokio::test]
+async fn test_validate_ethereum_eip712_signature() {
+    use common::utils::{program_test_with_domain, sign_send_instructions};
+    use sns_records::instruction::validate_ethereum_signature::eip712_message_hash;
+    use solana_program::keccak::hashv;
+
+    let alice = Keypair::new();
+    let domain = Keypair::new().pubkey();
+
+    let program_test = program_test_with_domain(&alice.pubkey(), &domain);
+    let mut prg_test_ctx = program_test.start_with_context().await;
+
+    let record = "eth";
+    let (record_key, _) = get_record_key_and_seeds(&domain, record);
+    let content = "0x4bfbfd1e018f9f27eeb788160579daf7e2cd7da7".as_bytes();
+
+    let eth_secret_key = libsecp256k1::SecretKey::parse(&[7; 32]).unwrap();
+    let eth_public_key = libsecp256k1::PublicKey::from_secret_key(&eth_secret_key);
+    let expected_pubkey = hashv(&[&eth_public_key.serialize()[1..]]).as_ref()[12..].to_vec();
+
+    ////
+    // Post a record with a staleness
+    ////
+    let ixs = vec![
+        sns_records::instruction::allocate_and_post_record(
+            sns_records::instruction::allocate_and_post_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::allocate_and_post_record::Params {
+                content: content.to_vec(),
+                record: record.to_owned(),
+            },
+        ),
+        validate_solana_signature(
+            validate_solana_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+                verifier: &alice.pubkey(),
+            },
+            validate_solana_signature::Params { staleness: true },
+        ),
+    ];
+    sign_send_instructions(&mut prg_test_ctx, ixs, vec![&alice])
+        .await
+        .unwrap();
+
+    ////
+    // Sign the typed data
+    ////
+    let hash = eip712_message_hash(content, &record_key, &alice.pubkey());
+    let (signature, recovery_id) =
+        libsecp256k1::sign(&libsecp256k1::Message::parse(&hash), &eth_secret_key);
+    let signature = [
+        signature.serialize().as_ref(),
+        &[recovery_id.serialize() + 27],
+    ]
+    .concat();
+
+    let validate_ix = |format| {
+        sns_records::instruction::validate_ethereum_signature_v2(
+            sns_records::instruction::validate_ethereum_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::validate_ethereum_signature::ParamsV2 {
+                validation: Validation::Ethereum,
+                signature: signature.clone(),
+                expected_pubkey: expected_pubkey.clone(),
+                format,
+            },
+        )
+    };
+
+    ////
+    // The typed data signature is not valid as a personal message
+    ////
+    let res = sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![validate_ix(EthereumMessageFormat::Eip191)],
+        vec![&alice],
+    )
+    .await;
+    assert!(res.is_err());
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![validate_ix(EthereumMessageFormat::Eip712)],
+        vec![&alice],
+    )
+    .await
+    .unwrap();
+
+    ////
+    // State verification
+    ////
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(record_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
+    assert_eq!(record_hd.content_length, content.len() as u32);
+    assert_eq!(record_hd.staleness_validation, Validation::Solana as u16);
+    assert_eq!(
+        record_hd.right_of_association_validation,
+        Validation::Ethereum as u16
+    );
+    assert_eq!(
+        &account.data[NameRecordHeader::LEN + RecordHeader::LEN..],
+        [alice.pubkey().as_ref(), &expected_pubkey, content].concat()
+    );
+}
+
+#[tokio::test]
+async fn test_validate_bitcoin_signature() {
+    use common::utils::{program_test_with_domain, sign_send_instructions};
+    use sns_records::{
+        instruction::validate_bitcoin_signature::message_to_sign,
+        state::validation::BitcoinAddressType,
+    };
+    use solana_program::hash::hash;
+
+    let alice = Keypair::new();
+    let domain = Keypair::new().pubkey();
+
+    let program_test = program_test_with_domain(&alice.pubkey(), &domain);
+    let mut prg_test_ctx = program_test.start_with_context().await;
+
+    let record = "btc";
+    let (record_key, _) = get_record_key_and_seeds(&domain, record);
+    let content = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".as_bytes();
+
+    // The secret key 1, its public key is the generator point
+    let mut secret_key = [0; 32];
+    secret_key[31] = 1;
+    let btc_secret_key = libsecp256k1::SecretKey::parse(&secret_key).unwrap();
+    // bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+    let p2wpkh_hash = [
+        0x75, 0x1e, 0x76, 0xe8, 0x19, 0x91, 0x96, 0xd4, 0x54, 0x94, 0x1c, 0x45, 0xd1, 0xb3, 0xa3,
+        0x23, 0xf1, 0x43, 0x3b, 0xd6,
+    ];
+    // 1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm
+    let p2pkh_hash = [
+        0x91, 0xb2, 0x4b, 0xf9, 0xf5, 0x28, 0x85, 0x32, 0x96, 0x0a, 0xc6, 0x87, 0xab, 0xb0, 0x35,
+        0x12, 0x7b, 0x1d, 0x28, 0xa5,
+    ];
+
+    ////
+    // Post a record with a staleness
+    ////
+    let ixs = vec![
+        sns_records::instruction::allocate_and_post_record(
+            sns_records::instruction::allocate_and_post_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::allocate_and_post_record::Params {
+                content: content.to_vec(),
+                record: record.to_owned(),
+            },
+        ),
+        validate_solana_signature(
+            validate_solana_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+                verifier: &alice.pubkey(),
+            },
+            validate_solana_signature::Params { staleness: true },
+        ),
+    ];
+    sign_send_instructions(&mut prg_test_ctx, ixs, vec![&alice])
+        .await
+        .unwrap();
+
+    let message = message_to_sign(content, &record_key, &alice.pubkey());
+    let message_hash = hash(hash(&message).as_ref());
+    let (signature, recovery_id) = libsecp256k1::sign(
+        &libsecp256k1::Message::parse(&message_hash.to_bytes()),
+        &btc_secret_key,
+    );
+    let sign = |header_base: u8| {
+        [
+            &[header_base + recovery_id.serialize()],
+            signature.serialize().as_ref(),
+        ]
+        .concat()
+    };
+    let validate_ix = |signature, address_type, expected_pubkey_hash| {
+        sns_records::instruction::validate_bitcoin_signature(
+            sns_records::instruction::validate_bitcoin_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::validate_bitcoin_signature::Params {
+                signature,
+                address_type,
+                expected_pubkey_hash,
+            },
+        )
+    };
+
+    ////
+    // SegWit addresses require a compressed public key
+    ////
+    let res = sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![validate_ix(
+            sign(27),
+            BitcoinAddressType::P2wpkh,
+            p2wpkh_hash,
+        )],
+        vec![&alice],
+    )
+    .await;
+    assert!(res.is_err());
+
+    ////
+    // The header must match the address type, P2SH-P2WPKH headers are rejected
+    ////
+    for (header_base, address_type) in [
+        (31, BitcoinAddressType::P2wpkh),
+        (35, BitcoinAddressType::P2wpkh),
+        (35, BitcoinAddressType::P2pkh),
+        (39, BitcoinAddressType::P2pkh),
+    ] {
+        let res = sign_send_instructions(
+            &mut prg_test_ctx,
+            vec![validate_ix(sign(header_base), address_type, p2wpkh_hash)],
+            vec![&alice],
+        )
+        .await;
+        assert!(res.is_err());
+    }
+
+    ////
+    // Signatures must be exactly 65 bytes long
+    ////
+    let res = sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![validate_ix(
+            [sign(39), vec![0]].concat(),
+            BitcoinAddressType::P2wpkh,
+            p2wpkh_hash,
+        )],
+        vec![&alice],
+    )
+    .await;
+    assert!(res.is_err());
+
+    ////
+    // P2WPKH signature
+    ////
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![validate_ix(
+            sign(39),
+            BitcoinAddressType::P2wpkh,
+            p2wpkh_hash,
+        )],
+        vec![&alice],
+    )
+    .await
+    .unwrap();
+
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(record_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
+    assert_eq!(record_hd.content_length, content.len() as u32);
+    assert_eq!(record_hd.staleness_validation, Validation::Solana as u16);
+    assert_eq!(
+        record_hd.right_of_association_validation,
+        Validation::Bitcoin as u16
+    );
+    assert_eq!(
+        &account.data[NameRecordHeader::LEN + RecordHeader::LEN..],
+        [
+            alice.pubkey().as_ref(),
+            &[BitcoinAddressType::P2wpkh as u8],
+            &p2wpkh_hash,
+            content
+        ]
+        .concat()
+    );
+
+    ////
+    // Legacy P2PKH signature with an uncompressed public key
+    ////
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![validate_ix(sign(27), BitcoinAddressType::P2pkh, p2pkh_hash)],
+        vec![&alice],
+    )
+    .await
+    .unwrap();
+
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(record_key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        &account.data[NameRecordHeader::LEN + RecordHeader::LEN..],
+        [
+            alice.pubkey().as_ref(),
+            &[BitcoinAddressType::P2pkh as u8],
+            &p2pkh_hash,
+            content
+        ]
+        .concat()
+    );
+}
+
+#[tokio::test]
+async fn test_validate_nostr_signature() {
+    use common::utils::{program_test_with_domain, sign_send_instructions};
+
+    // Same hardcoded keypair and domain as `test_functional` for the signature to remain constant
+    // Associated pubkey: 9K6vPLB1DqgznyA3CBKeZ3GnD8Fqo8vcvx2Vxkk5uwqN
+    let alice = Keypair::from_bytes(&[
+        42, 185, 156, 155, 46, 95, 163, 247, 19, 215, 251, 222, 166, 74, 236, 11, 8, 248, 245, 184,
+        40, 127, 236, 213, 229, 186, 144, 210, 89, 137, 115, 230, 123, 128, 164, 236, 16, 182, 19,
+        26, 12, 250, 103, 12, 136, 205, 152, 26, 138, 58, 99, 22, 166, 119, 18, 252, 89, 145, 162,
+        209, 100, 137, 15, 13,
+    ])
+    .unwrap();
+    let domain = Pubkey::from_str("7nf2Rq9DxwQCTg1ZmEEB5VUVAzq6tGpsYxqJ6JHqyoTQ").unwrap();
+
+    let program_test = program_test_with_domain(&alice.pubkey(), &domain);
+    let mut prg_test_ctx = program_test.start_with_context().await;
+
+    let record = "nostr";
+    // Record key J5P8GU5oar5d34hwEcf4k4Jw2qtmikMnYPgoTtiNMeBm
+    let (record_key, _) = get_record_key_and_seeds(&domain, record);
+    let content = "npub1adpcct2vly2sskms7ehyafwal4s8ykstaqk805zzmxyge9cx02yqj8wcqr".as_bytes();
+
+    // Nostr secret key [0x4e; 32]
+    let expected_pubkey = [
+        235, 67, 140, 45, 76, 249, 21, 8, 91, 112, 246, 110, 78, 165, 221, 253, 96, 114, 90, 11,
+        232, 44, 119, 208, 66, 217, 136, 140, 151, 6, 122, 136,
+    ];
+    let signature = [
+        7, 33, 193, 78, 85, 192, 235, 31, 139, 100, 96, 154, 254, 110, 94, 55, 224, 250, 228, 27,
+        2, 205, 196, 37, 182, 14, 109, 88, 31, 189, 188, 93, 244, 132, 127, 18, 0, 173, 76, 179,
+        125, 62, 245, 225, 225, 156, 249, 243, 48, 62, 130, 212, 19, 231, 239, 247, 159, 88, 20,
+        37, 219, 204, 155, 143,
+    ];
+
+    ////
+    // Post a record with a staleness
+    ////
+    let ixs = vec![
+        sns_records::instruction::allocate_and_post_record(
+            sns_records::instruction::allocate_and_post_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::allocate_and_post_record::Params {
+                content: content.to_vec(),
+                record: record.to_owned(),
+            },
+        ),
+        validate_solana_signature(
+            validate_solana_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+                verifier: &alice.pubkey(),
+            },
+            validate_solana_signature::Params { staleness: true },
+        ),
+    ];
+    sign_send_instructions(&mut prg_test_ctx, ixs, vec![&alice])
+        .await
+        .unwrap();
+
+    let validate_ix = |signature| {
+        sns_records::instruction::validate_nostr_signature(
+            sns_records::instruction::validate_nostr_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::validate_nostr_signature::Params {
+                signature,
+                expected_pubkey,
+            },
+        )
+    };
+
+    ////
+    // A tampered signature is rejected
+    ////
+    let mut tampered_signature = signature;
+    tampered_signature[63] ^= 1;
+    let res = sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![validate_ix(tampered_signature)],
+        vec![&alice],
+    )
+    .await;
+    assert!(res.is_err());
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![validate_ix(signature)],
+        vec![&alice],
+    )
+    .await
+    .unwrap();
+
+    ////
+    // State verification
+    ////
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(record_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
+    assert_eq!(record_hd.content_length, content.len() as u32);
+    assert_eq!(record_hd.staleness_validation, Validation::Solana as u16);
+    assert_eq!(
+        record_hd.right_of_association_validation,
+        Validation::Nostr as u16
+    );
+    assert_eq!(
+        &account.data[NameRecordHeader::LEN + RecordHeader::LEN..],
+        [alice.pubkey().as_ref(), &expected_pubkey, content].concat()
+    );
+}
+
+#[tokio::test]
+async fn test_write_unverified_ethereum_roa() {
+    use common::utils::{program_test_with_domain, sign_send_instructions};
+    use sns_records::instruction::validate_ethereum_signature::{eth_address, sign_record};
+
+    let alice = Keypair::new();
+    let domain = Keypair::new().pubkey();
+
+    let program_test = program_test_with_domain(&alice.pubkey(), &domain);
+    let mut prg_test_ctx = program_test.start_with_context().await;
+
+    let record = "eth";
+    let (record_key, _) = get_record_key_and_seeds(&domain, record);
+    let content = "0x4bfbfd1e018f9f27eeb788160579daf7e2cd7da7".as_bytes();
+
+    let eth_secret_key = libsecp256k1::SecretKey::parse(&[7; 32]).unwrap();
+    let eth_public_key = eth_address(&libsecp256k1::PublicKey::from_secret_key(&eth_secret_key));
+    let other_secret_key = libsecp256k1::SecretKey::parse(&[8; 32]).unwrap();
+    let other_public_key =
+        eth_address(&libsecp256k1::PublicKey::from_secret_key(&other_secret_key));
+
+    ////
+    // Post a record with a staleness
+    ////
+    let ixs = vec![
+        sns_records::instruction::allocate_and_post_record(
+            sns_records::instruction::allocate_and_post_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::allocate_and_post_record::Params {
+                content: content.to_vec(),
+                record: record.to_owned(),
+            },
+        ),
+        validate_solana_signature(
+            validate_solana_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+                verifier: &alice.pubkey(),
+            },
+            validate_solana_signature::Params { staleness: true },
+        ),
+    ];
+    sign_send_instructions(&mut prg_test_ctx, ixs, vec![&alice])
+        .await
+        .unwrap();
+
+    let write_roa_ix = |validation, roa_id: &[u8]| {
+        sns_records::instruction::write_roa_v2(
+            sns_records::instruction::write_roa::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::write_roa::ParamsV2 {
+                validation,
+                roa_id: roa_id.to_vec(),
+            },
+        )
+    };
+
+    ////
+    // The RoA ID must match the length of the kind, which must be unverified
+    ////
+    for (validation, roa_id) in [
+        (
+            Validation::UnverifiedEthereum,
+            alice.pubkey().to_bytes().to_vec(),
+        ),
+        (Validation::UnverifiedSolana, eth_public_key.to_vec()),
+        (Validation::Ethereum, eth_public_key.to_vec()),
+    ] {
+        let res = sign_send_instructions(
+            &mut prg_test_ctx,
+            vec![write_roa_ix(validation, &roa_id)],
+            vec![&alice],
+        )
+        .await;
+        assert!(res.is_err());
+    }
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![write_roa_ix(
+            Validation::UnverifiedEthereum,
+            &eth_public_key,
+        )],
+        vec![&alice],
+    )
+    .await
+    .unwrap();
+
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(record_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
+    assert_eq!(
+        record_hd.right_of_association_validation,
+        Validation::UnverifiedEthereum as u16
+    );
+    assert_eq!(
+        &account.data[NameRecordHeader::LEN + RecordHeader::LEN..],
+        [alice.pubkey().as_ref(), &eth_public_key, content].concat()
+    );
+
+    let validate_ix = |secret_key, expected_pubkey: &[u8]| {
+        sns_records::instruction::validate_ethereum_signature(
+            sns_records::instruction::validate_ethereum_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::validate_ethereum_signature::Params {
+                validation: Validation::Ethereum,
+                signature: sign_record(
+                    secret_key,
+                    EthereumMessageFormat::Eip191,
+                    content,
+                    &record_key,
+                    &alice.pubkey(),
+                )
+                .to_vec(),
+                expected_pubkey: expected_pubkey.to_vec(),
+            },
+        )
+    };
+
+    ////
+    // Only the declared key can prove the RoA
+    ////
+    let res = sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![validate_ix(&other_secret_key, &other_public_key)],
+        vec![&alice],
+    )
+    .await;
+    assert!(res.is_err());
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![validate_ix(&eth_secret_key, &eth_public_key)],
+        vec![&alice],
+    )
+    .await
+    .unwrap();
+
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(record_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
+    assert_eq!(
+        record_hd.right_of_association_validation,
+        Validation::Ethereum as u16
+    );
+    assert_eq!(
+        &account.data[NameRecordHeader::LEN + RecordHeader::LEN..],
+        [alice.pubkey().as_ref(), &eth_public_key, content].concat()
+    );
+}
+
+#[tokio::test]
+async fn test_validate_ethereum_xchain_signature() {
+    use common::utils::{add_xchain_domain, new_program_test, sign_send_instructions};
+    use sns_records::instruction::validate_ethereum_signature::{
+        eth_address, message_for_format, sign_message, xchain_message_for_format,
+    };
+    use sns_warp_common::{state::x_domain::XDomain, wormhole::ChainIds};
+
+    let alice = Keypair::new();
+    // The domain owner, an account of the SNS Warp emitter
+    let x_owner = Keypair::new();
+    let domain = Keypair::new().pubkey();
+
+    let eth_secret_key = libsecp256k1::SecretKey::parse(&[7; 32]).unwrap();
+    let eth_public_key = eth_address(&libsecp256k1::PublicKey::from_secret_key(&eth_secret_key));
+    let mut owner_address = [0; 32];
+    owner_address[12..].copy_from_slice(&eth_public_key);
+    let chain_id = ChainIds::BASE as u16;
+
+    let mut program_test = new_program_test();
+    program_test.add_account(
+        alice.pubkey(),
+        Account {
+            lamports: 100_000_000_000,
+            ..Account::default()
+        },
+    );
+    add_xchain_domain(
+        &mut program_test,
+        &x_owner.pubkey(),
+        &XDomain {
+            nonce: 255,
+            owner_chain: chain_id,
+            owner_address,
+            name_account: domain,
+        },
+    );
+    let mut prg_test_ctx = program_test.start_with_context().await;
+
+    let record = "eth";
+    let (record_key, _) = get_record_key_and_seeds(&domain, record);
+    let content = "0x4bfbfd1e018f9f27eeb788160579daf7e2cd7da7".as_bytes();
+
+    ////
+    // Post a record with a XChain staleness
+    ////
+    let ixs = vec![
+        sns_records::instruction::allocate_and_post_record(
+            sns_records::instruction::allocate_and_post_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &x_owner.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::allocate_and_post_record::Params {
+                content: content.to_vec(),
+                record: record.to_owned(),
+            },
+        ),
+        validate_solana_signature(
+            validate_solana_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &x_owner.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+                verifier: &x_owner.pubkey(),
+            },
+            validate_solana_signature::Params { staleness: true },
+        ),
+    ];
+    sign_send_instructions(&mut prg_test_ctx, ixs, vec![&alice, &x_owner])
+        .await
+        .unwrap();
+
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(record_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
+    assert_eq!(record_hd.staleness_validation, Validation::XChain as u16);
+
+    let validate_ix = |signature: Vec<u8>, format| {
+        sns_records::instruction::validate_ethereum_signature_v2(
+            sns_records::instruction::validate_ethereum_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &x_owner.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::validate_ethereum_signature::ParamsV2 {
+                validation: Validation::Ethereum,
+                signature,
+                expected_pubkey: eth_public_key.to_vec(),
+                format,
+            },
+        )
+    };
+
+    ////
+    // A message built for a Solana staleness ID is rejected
+    ////
+    let staleness_id = Pubkey::new_from_array(owner_address);
+    let signature = sign_message(
+        &eth_secret_key,
+        &message_for_format(
+            EthereumMessageFormat::Eip191,
+            content,
+            &record_key,
+            &staleness_id,
+        ),
+    );
+    let res = sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![validate_ix(
+            signature.to_vec(),
+            EthereumMessageFormat::Eip191,
+        )],
+        vec![&alice, &x_owner],
+    )
+    .await;
+    assert!(res.is_err());
+
+    for format in [EthereumMessageFormat::Eip191, EthereumMessageFormat::Eip712] {
+        let signature = sign_message(
+            &eth_secret_key,
+            &xchain_message_for_format(format, content, &record_key, chain_id, &owner_address),
+        );
+        sign_send_instructions(
+            &mut prg_test_ctx,
+            vec![validate_ix(signature.to_vec(), format)],
+            vec![&alice, &x_owner],
+        )
+        .await
+        .unwrap();
+    }
+
+    ////
+    // State verification
+    ////
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(record_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
+    assert_eq!(record_hd.staleness_validation, Validation::XChain as u16);
+    assert_eq!(
+        record_hd.right_of_association_validation,
+        Validation::Ethereum as u16
+    );
+    assert_eq!(
+        &account.data[NameRecordHeader::LEN + RecordHeader::LEN..],
+        [
+            &chain_id.to_le_bytes()[..],
+            &owner_address,
+            &eth_public_key,
+            content
+        ]
+        .concat()
+    );
+}
+
+#[tokio::test]
+async fn test_validate_passkey_signature() {
+    use common::utils::{program_test_with_domain, sign_send_instructions};
+    use sns_records::{
+        instruction::validate_passkey_signature::{challenge, new_secp256r1_instruction},
+        precompile::SECP256R1_PROGRAM_ID,
+    };
+    use solana_program::{
+        account_info::AccountInfo, entrypoint::ProgramResult, hash::hash, sysvar::instructions,
+    };
+
+    // The secp256r1 precompile is not available in the test validator
+    fn process_secp256r1(_: &Pubkey, _: &[AccountInfo], _: &[u8]) -> ProgramResult {
+        Ok(())
+    }
+
+    // Same hardcoded keypair and domain as `test_functional` for the signature to remain constant
+    // Associated pubkey: 9K6vPLB1DqgznyA3CBKeZ3GnD8Fqo8vcvx2Vxkk5uwqN
+    let alice = Keypair::from_bytes(&[
+        42, 185, 156, 155, 46, 95, 163, 247, 19, 215, 251, 222, 166, 74, 236, 11, 8, 248, 245, 184,
+        40, 127, 236, 213, 229, 186, 144, 210, 89, 137, 115, 230, 123, 128, 164, 236, 16, 182, 19,
+        26, 12, 250, 103, 12, 136, 205, 152, 26, 138, 58, 99, 22, 166, 119, 18, 252, 89, 145, 162,
+        209, 100, 137, 15, 13,
+    ])
+    .unwrap();
+    let domain = Pubkey::from_str("7nf2Rq9DxwQCTg1ZmEEB5VUVAzq6tGpsYxqJ6JHqyoTQ").unwrap();
+
+    let mut program_test = program_test_with_domain(&alice.pubkey(), &domain);
+    program_test.add_program(
+        "secp256r1",
+        SECP256R1_PROGRAM_ID,
+        processor!(process_secp256r1),
+    );
+    let mut prg_test_ctx = program_test.start_with_context().await;
+
+    let record = "url";
+    // Record key Gve3rsR5bPZD5bWXBum13wHDEdwQUhwCPkqYirvj8oiu
+    let (record_key, _) = get_record_key_and_seeds(&domain, record);
+    let content = "https://sns.id".as_bytes();
+
+    // Passkey secret key: SHA-256 of "sns-records passkey"
+    let passkey = [
+        3, 9, 170, 72, 211, 184, 22, 62, 63, 155, 137, 121, 60, 249, 101, 5, 24, 185, 148, 2, 44,
+        38, 7, 121, 23, 241, 108, 205, 244, 54, 245, 217, 187,
+    ];
+    let signature = [
+        201, 103, 226, 94, 170, 145, 234, 228, 24, 148, 201, 138, 4, 112, 179, 93, 94, 216, 21, 26,
+        135, 93, 225, 101, 177, 161, 242, 197, 173, 233, 97, 238, 56, 183, 86, 161, 88, 42, 249,
+        26, 222, 60, 80, 198, 172, 123, 62, 246, 158, 157, 243, 221, 86, 250, 214, 159, 212, 172,
+        2, 99, 109, 70, 227, 55,
+    ];
+    // RP ID hash, user present and verified flags, signature counter
+    let authenticator_data = [hash(b"sns.id").as_ref(), &[0x05], &[0; 4]].concat();
+    let client_data_json = |content: &[u8]| {
+        format!(
+            r#"{{"type":"webauthn.get","challenge":"{}","origin":"https://sns.id","crossOrigin":false}}"#,
+            base64::Engine::encode(
+                &base64::engine::general_purpose::URL_SAFE_NO_PAD,
+                challenge(
+                    &record_key,
+                    Validation::Solana,
+                    alice.pubkey().as_ref(),
+                    content
+                )
+            )
+        )
+        .into_bytes()
+    };
+
+    ////
+    // Post a record with a staleness
+    ////
+    let ixs = vec![
+        sns_records::instruction::allocate_and_post_record(
+            sns_records::instruction::allocate_and_post_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::allocate_and_post_record::Params {
+                content: content.to_vec(),
+                record: record.to_owned(),
+            },
+        ),
+        validate_solana_signature(
+            validate_solana_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+                verifier: &alice.pubkey(),
+            },
+            validate_solana_signature::Params { staleness: true },
+        ),
+    ];
+    sign_send_instructions(&mut prg_test_ctx, ixs, vec![&alice])
+        .await
+        .unwrap();
+
+    let validate_ixs = |client_data_json: Vec<u8>| {
+        vec![
+            new_secp256r1_instruction(&passkey, &signature, &authenticator_data, &client_data_json),
+            sns_records::instruction::validate_passkey_signature(
+                sns_records::instruction::validate_passkey_signature::Accounts {
+                    system_program: &system_program::ID,
+                    spl_name_service_program: &spl_name_service::ID,
+                    fee_payer: &alice.pubkey(),
+                    record: &record_key,
+                    domain: &domain,
+                    domain_owner: &alice.pubkey(),
+                    central_state: &sns_records::central_state::KEY,
+                    instructions_sysvar: &instructions::ID,
+                },
+                sns_records::instruction::validate_passkey_signature::Params {
+                    authenticator_data: authenticator_data.clone(),
+                    client_data_json,
+                },
+            ),
+        ]
+    };
+
+    ////
+    // The challenge must match the record
+    ////
+    let res = sign_send_instructions(
+        &mut prg_test_ctx,
+        validate_ixs(client_data_json(b"https://bonfida.org")),
+        vec![&alice],
+    )
+    .await;
+    assert!(res.is_err());
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        validate_ixs(client_data_json(content)),
+        vec![&alice],
+    )
+    .await
+    .unwrap();
+
+    ////
+    // State verification
+    ////
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(record_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
+    assert_eq!(record_hd.content_length, content.len() as u32);
+    assert_eq!(record_hd.staleness_validation, Validation::Solana as u16);
+    assert_eq!(
+        record_hd.right_of_association_validation,
+        Validation::Passkey as u16
+    );
+    assert_eq!(
+        &account.data[NameRecordHeader::LEN + RecordHeader::LEN..],
+        [alice.pubkey().as_ref(), &passkey, content].concat()
+    );
+}
+
+#[tokio::test]
+async fn test_validate_signature() {
+    use common::utils::{program_test_with_domain, sign_send_instructions};
+    use sns_records::{
+        error::SnsRecordsError, instruction::validate_signature,
+        state::validation::get_validation_length, verifier::get_verifier,
+    };
+
+    // Same hardcoded keypair and domain as `test_functional` for the signature to remain constant
+    // Associated pubkey: 9K6vPLB1DqgznyA3CBKeZ3GnD8Fqo8vcvx2Vxkk5uwqN
+    let alice = Keypair::from_bytes(&[
+        42, 185, 156, 155, 46, 95, 163, 247, 19, 215, 251, 222, 166, 74, 236, 11, 8, 248, 245, 184,
+        40, 127, 236, 213, 229, 186, 144, 210, 89, 137, 115, 230, 123, 128, 164, 236, 16, 182, 19,
+        26, 12, 250, 103, 12, 136, 205, 152, 26, 138, 58, 99, 22, 166, 119, 18, 252, 89, 145, 162,
+        209, 100, 137, 15, 13,
+    ])
+    .unwrap();
+    let domain = Pubkey::from_str("7nf2Rq9DxwQCTg1ZmEEB5VUVAzq6tGpsYxqJ6JHqyoTQ").unwrap();
+
+    let program_test = program_test_with_domain(&alice.pubkey(), &domain);
+    let mut prg_test_ctx = program_test.start_with_context().await;
+
+    // Same record as `test_validate_nostr_signature`
+    let record = "nostr";
+    let (record_key, _) = get_record_key_and_seeds(&domain, record);
+    let content = "npub1adpcct2vly2sskms7ehyafwal4s8ykstaqk805zzmxyge9cx02yqj8wcqr".as_bytes();
+
+    // Nostr secret key [0x4e; 32]
+    let nostr_pubkey = [
+        235, 67, 140, 45, 76, 249, 21, 8, 91, 112, 246, 110, 78, 165, 221, 253, 96, 114, 90, 11,
+        232, 44, 119, 208, 66, 217, 136, 140, 151, 6, 122, 136,
+    ];
+    let signature = [
+        7, 33, 193, 78, 85, 192, 235, 31, 139, 100, 96, 154, 254, 110, 94, 55, 224, 250, 228, 27,
+        2, 205, 196, 37, 182, 14, 109, 88, 31, 189, 188, 93, 244, 132, 127, 18, 0, 173, 76, 179,
+        125, 62, 245, 225, 225, 156, 249, 243, 48, 62, 130, 212, 19, 231, 239, 247, 159, 88, 20,
+        37, 219, 204, 155, 143,
+    ];
+
+    ////
+    // Post a record with a staleness
+    ////
+    let ixs = vec![
+        sns_records::instruction::allocate_and_post_record(
+            sns_records::instruction::allocate_and_post_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::allocate_and_post_record::Params {
+                content: content.to_vec(),
+                record: record.to_owned(),
+            },
+        ),
+        validate_solana_signature(
+            validate_solana_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+                verifier: &alice.pubkey(),
+            },
+            validate_solana_signature::Params { staleness: true },
+        ),
+    ];
+    sign_send_instructions(&mut prg_test_ctx, ixs, vec![&alice])
+        .await
+        .unwrap();
+
+    let validate_ix = |validation, roa_id: &[u8]| {
+        sns_records::instruction::validate_signature(
+            validate_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            validate_signature::Params {
+                validation,
+                signature: signature.to_vec(),
+                roa_id: roa_id.to_vec(),
+            },
+        )
+    };
+
+    ////
+    // Validations without a signature verifier are rejected, Solana and Passkey RoAs are verified
+    // by the precompiles
+    ////
+    for validation in [Validation::Solana, Validation::Passkey] {
+        assert!(matches!(
+            get_verifier(validation),
+            Err(SnsRecordsError::UnsupportedValidation)
+        ));
+        let roa_id = vec![2; get_validation_length(validation) as usize];
+        let res = sign_send_instructions(
+            &mut prg_test_ctx,
+            vec![validate_ix(validation, &roa_id)],
+            vec![&alice],
+        )
+        .await;
+        assert!(res.is_err());
+    }
+
+    ////
+    // RoA IDs of the wrong length are rejected
+    ////
+    let res = sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![validate_ix(Validation::Nostr, &nostr_pubkey[..31])],
+        vec![&alice],
+    )
+    .await;
+    assert!(res.is_err());
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![validate_ix(Validation::Nostr, &nostr_pubkey)],
+        vec![&alice],
+    )
+    .await
+    .unwrap();
+
+    ////
+    // State verification
+    ////
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(record_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
+    assert_eq!(record_hd.content_length, content.len() as u32);
+    assert_eq!(record_hd.staleness_validation, Validation::Solana as u16);
+    assert_eq!(
+        record_hd.right_of_association_validation,
+        Validation::Nostr as u16
+    );
+    assert_eq!(
+        &account.data[NameRecordHeader::LEN + RecordHeader::LEN..],
+        [alice.pubkey().as_ref(), &nostr_pubkey, content].concat()
+    );
+}
+
+#[tokio::test]
+async fn test_migrate_record() {
+    use common::utils::{program_test_with_domain, sign_send_instructions};
+    use sns_records::state::{
+        record::Record,
+        record_header::{RecordHeaderV2, VersionedRecordHeader},
+    };
+
+    let alice = Keypair::new();
+    let domain = Keypair::new().pubkey();
+
+    let program_test = program_test_with_domain(&alice.pubkey(), &domain);
+    let mut prg_test_ctx = program_test.start_with_context().await;
+
+    let record = "TXT";
+    let (record_key, _) = get_record_key_and_seeds(&domain, record);
+    let content = "hello world".as_bytes();
+
+    ////
+    // Post a V1 record with a staleness
+    ////
+    let ixs = vec![
+        sns_records::instruction::allocate_and_post_record(
+            sns_records::instruction::allocate_and_post_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::allocate_and_post_record::Params {
+                content: content.to_vec(),
+                record: record.to_owned(),
+            },
+        ),
+        validate_solana_signature(
+            validate_solana_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+                verifier: &alice.pubkey(),
+            },
+            validate_solana_signature::Params { staleness: true },
+        ),
+    ];
+    sign_send_instructions(&mut prg_test_ctx, ixs, vec![&alice])
+        .await
+        .unwrap();
+
+    let migrate_ix = |domain_owner: &Pubkey| {
+        sns_records::instruction::migrate_record(
+            sns_records::instruction::migrate_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner,
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::migrate_record::Params {},
+        )
+    };
+
+    ////
+    // Only the domain owner can migrate the record
+    ////
+    let bob = Keypair::new();
+    let res = sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![migrate_ix(&bob.pubkey())],
+        vec![&alice, &bob],
+    )
+    .await;
+    assert!(res.is_err());
+
+    ////
+    // Migrate the record
+    ////
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![migrate_ix(&alice.pubkey())],
+        vec![&alice],
+    )
+    .await
+    .unwrap();
+
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(record_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let header = match VersionedRecordHeader::try_from_buffer(&account.data).unwrap() {
+        VersionedRecordHeader::V2(header) => header,
+        header => panic!("Unexpected header {:?}", header),
+    };
+    assert_eq!(header.version, RecordHeaderV2::VERSION);
+    assert_eq!(header.flags, 0);
+    assert_eq!(header.content_length, content.len() as u32);
+    assert_eq!(header.staleness_validation, Validation::Solana as u16);
+    assert_eq!(
+        header.right_of_association_validation,
+        Validation::None as u16
+    );
+    assert_eq!(
+        &account.data[NameRecordHeader::LEN + RecordHeaderV2::LEN..],
+        [alice.pubkey().as_ref(), content].concat()
+    );
+    let parsed = Record::deserialize(&account.data).unwrap();
+    assert_eq!(parsed.staleness_id(), alice.pubkey().as_ref());
+    assert_eq!(parsed.content(), content);
+
+    ////
+    // A V2 record cannot be migrated again
+    ////
+    prg_test_ctx.last_blockhash = prg_test_ctx.get_new_latest_blockhash().await.unwrap();
+    let res = sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![migrate_ix(&alice.pubkey())],
+        vec![&alice],
+    )
+    .await;
+    assert!(res.is_err());
+
+    ////
+    // Instructions rewriting the header keep the V2 layout
+    ////
+    let ixs = vec![
+        sns_records::instruction::write_roa(
+            sns_records::instruction::write_roa::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::write_roa::Params {
+                roa_id: alice.pubkey().to_bytes().to_vec(),
+            },
+        ),
+        validate_solana_signature(
+            validate_solana_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+                verifier: &alice.pubkey(),
+            },
+            validate_solana_signature::Params { staleness: false },
+        ),
+        sns_records::instruction::patch_record(
+            sns_records::instruction::patch_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::patch_record::Params {
+                patches: vec![sns_records::instruction::patch_record::Patch {
+                    offset: 0,
+                    data: b"H".to_vec(),
+                }],
+            },
+        ),
+    ];
+    sign_send_instructions(&mut prg_test_ctx, ixs, vec![&alice])
+        .await
+        .unwrap();
+
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(record_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let header = VersionedRecordHeader::try_from_buffer(&account.data).unwrap();
+    assert!(matches!(header, VersionedRecordHeader::V2(_)));
+    assert_eq!(
+        header.header().right_of_association_validation,
+        Validation::Solana as u16
+    );
+    assert_eq!(
+        &account.data[NameRecordHeader::LEN + RecordHeaderV2::LEN..],
+        [
+            alice.pubkey().as_ref(),
+            alice.pubkey().as_ref(),
+            "Hello world".as_bytes()
+        ]
+        .concat()
+    );
+
+    let content = "https://sns.id".as_bytes();
+    let ix = sns_records::instruction::edit_record(
+        sns_records::instruction::edit_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &alice.pubkey(),
+            record: &record_key,
+            domain: &domain,
+            domain_owner: &alice.pubkey(),
+            central_state: &sns_records::central_state::KEY,
+        },
+        sns_records::instruction::edit_record::Params {
+            record: record.to_owned(),
+            content: content.to_vec(),
+        },
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice])
+        .await
+        .unwrap();
+
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(record_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
+    assert_eq!(record_hd.content_length, content.len() as u32);
+    assert_eq!(record_hd.staleness_validation, Validation::None as u16);
+    assert_eq!(
+        account.data.len(),
+        NameRecordHeader::LEN + RecordHeaderV2::LEN + content.len()
+    );
+    assert_eq!(
+        Record::deserialize(&account.data).unwrap().content(),
+        content
+    );
+}
+
+#[tokio::test]
+async fn test_xchain_record_operation() {
+    use borsh::BorshDeserialize;
+    use common::utils::{add_xchain_domain, new_program_test, sign_send_instructions};
+    use sns_records::{
+        instruction::{
+            validate_ethereum_signature::{eth_address, sign_message},
+            xchain_record_operation::{self, message_to_sign, XChainOperation},
+        },
+        state::xchain_nonce::XChainNonce,
+    };
+    use sns_warp_common::{state::x_domain::XDomain, wormhole::ChainIds};
+
+    // The relayer of the authorizations
+    let alice = Keypair::new();
+    // The domain owner, an account of the SNS Warp emitter
+    let x_owner = Keypair::new();
+    let domain = Keypair::new().pubkey();
+    let injective_domain = Keypair::new().pubkey();
+
+    let eth_secret_key = libsecp256k1::SecretKey::parse(&[7; 32]).unwrap();
+    let eth_public_key = eth_address(&libsecp256k1::PublicKey::from_secret_key(&eth_secret_key));
+    let mut owner_address = [0; 32];
+    owner_address[12..].copy_from_slice(&eth_public_key);
+
+    let mut program_test = new_program_test();
+    program_test.add_account(
+        alice.pubkey(),
+        Account {
+            lamports: 100_000_000_000,
+            ..Account::default()
+        },
+    );
+    add_xchain_domain(
+        &mut program_test,
+        &x_owner.pubkey(),
+        &XDomain {
+            nonce: 255,
+            owner_chain: ChainIds::BASE as u16,
+            owner_address,
+            name_account: domain,
+        },
+    );
+    // A domain owned on Injective
+    let injective_owner = Keypair::new();
+    add_xchain_domain(
+        &mut program_test,
+        &injective_owner.pubkey(),
+        &XDomain {
+            nonce: 255,
+            owner_chain: ChainIds::Injective as u16,
+            owner_address,
+            name_account: injective_domain,
+        },
+    );
+    let mut prg_test_ctx = program_test.start_with_context().await;
+
+    let record = "TXT";
+    let (record_key, _) = get_record_key_and_seeds(&domain, record);
+    let (nonce_key, _) = XChainNonce::find_key(&domain);
+
+    ////
+    // The record is allocated while the domain owner can still sign
+    ////
+    let ix = sns_records::instruction::allocate_and_post_record(
+        sns_records::instruction::allocate_and_post_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &alice.pubkey(),
+            record: &record_key,
+            domain: &domain,
+            domain_owner: &x_owner.pubkey(),
+            central_state: &sns_records::central_state::KEY,
+        },
+        sns_records::instruction::allocate_and_post_record::Params {
+            content: b"hello".to_vec(),
+            record: record.to_owned(),
+        },
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice, &x_owner])
+        .await
+        .unwrap();
+
+    let operation_ix = |operation: XChainOperation, nonce: u64, secret_key| {
+        let signature = sign_message(secret_key, &message_to_sign(&operation, &record_key, nonce));
+        sns_records::instruction::xchain_record_operation(
+            xchain_record_operation::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &x_owner.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+                nonce: &nonce_key,
+            },
+            xchain_record_operation::Params {
+                operation,
+                nonce,
+                signature: signature.to_vec(),
+                validate_content: true,
+            },
+        )
+    };
+    let edit = |content: &[u8]| XChainOperation::Edit {
+        record: record.to_owned(),
+        content: content.to_vec(),
+    };
+
+    ////
+    // Edit the record with the authorization of the foreign owner
+    ////
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![operation_ix(edit(b"hello world"), 0, &eth_secret_key)],
+        vec![&alice],
+    )
+    .await
+    .unwrap();
+
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(record_key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        &account.data[NameRecordHeader::LEN + RecordHeader::LEN..],
+        b"hello world"
+    );
+    let nonce = prg_test_ctx
+        .banks_client
+        .get_account(nonce_key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(nonce.owner, sns_records::ID);
+    assert_eq!(
+        XChainNonce::try_from_slice(&nonce.data).unwrap(),
+        XChainNonce { nonce: 1 }
+    );
+
+    ////
+    // Authorizations cannot be replayed or signed by another key
+    ////
+    let res = sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![operation_ix(edit(b"replayed"), 0, &eth_secret_key)],
+        vec![&alice],
+    )
+    .await;
+    assert!(res.is_err());
+
+    let other_secret_key = libsecp256k1::SecretKey::parse(&[8; 32]).unwrap();
+    let res = sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![operation_ix(edit(b"forged"), 1, &other_secret_key)],
+        vec![&alice],
+    )
+    .await;
+    assert!(res.is_err());
+
+    ////
+    // The content is validated against the type of the record
+    ////
+    let res = sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![operation_ix(
+            edit(&[b'a'; MAX_TEXT_LENGTH + 1]),
+            1,
+            &eth_secret_key,
+        )],
+        vec![&alice],
+    )
+    .await;
+    assert!(res.is_err());
+
+    ////
+    // Owners on chains other than the EVM ones cannot sign EIP-191 authorizations
+    ////
+    let (injective_record_key, _) = get_record_key_and_seeds(&injective_domain, record);
+    let (injective_nonce_key, _) = XChainNonce::find_key(&injective_domain);
+    let ix = sns_records::instruction::allocate_and_post_record(
+        sns_records::instruction::allocate_and_post_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &alice.pubkey(),
+            record: &injective_record_key,
+            domain: &injective_domain,
+            domain_owner: &injective_owner.pubkey(),
+            central_state: &sns_records::central_state::KEY,
+        },
+        sns_records::instruction::allocate_and_post_record::Params {
+            content: b"hello".to_vec(),
+            record: record.to_owned(),
+        },
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice, &injective_owner])
+        .await
+        .unwrap();
+    let operation = edit(b"hello injective");
+    let signature = sign_message(
+        &eth_secret_key,
+        &message_to_sign(&operation, &injective_record_key, 0),
+    );
+    let ix = sns_records::instruction::xchain_record_operation(
+        xchain_record_operation::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &alice.pubkey(),
+            record: &injective_record_key,
+            domain: &injective_domain,
+            domain_owner: &injective_owner.pubkey(),
+            central_state: &sns_records::central_state::KEY,
+            nonce: &injective_nonce_key,
+        },
+        xchain_record_operation::Params {
+            operation,
+            nonce: 0,
+            signature: signature.to_vec(),
+            validate_content: true,
+        },
+    );
+    let res = sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice]).await;
+    assert!(res.is_err());
+
+    ////
+    // Delete the record, the rent is refunded to the domain owner
+    ////
+    let lamports = prg_test_ctx
+        .banks_client
+        .get_balance(x_owner.pubkey())
+        .await
+        .unwrap();
+    let record_lamports = prg_test_ctx
+        .banks_client
+        .get_balance(record_key)
+        .await
+        .unwrap();
+    let delete = XChainOperation::Delete {
+        record: record.to_owned(),
+    };
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![operation_ix(delete, 1, &eth_secret_key)],
+        vec![&alice],
+    )
+    .await
+    .unwrap();
+
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(record_key)
+        .await
+        .unwrap();
+    assert!(account.is_none());
+    assert_eq!(
+        prg_test_ctx
+            .banks_client
+            .get_balance(x_owner.pubkey())
+            .await
+            .unwrap(),
+        lamports + record_lamports
+    );
+}
+
+#[tokio::test]
+async fn test_check_address_record() {
+    use common::utils::{program_test_with_domain, sign_send_instructions};
+    use sns_records::instruction::{check_address_record, unverify_roa, write_roa};
+
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let domain = Keypair::new().pubkey();
+
+    let program_test = program_test_with_domain(&alice.pubkey(), &domain);
+    let mut prg_test_ctx = program_test.start_with_context().await;
+
+    let record = "SOL";
+    let (record_key, _) = get_record_key_and_seeds(&domain, record);
+
+    ////
+    // Post the address of Alice
+    ////
+    let ixs = vec![
+        sns_records::instruction::allocate_and_post_record(
+            sns_records::instruction::allocate_and_post_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::allocate_and_post_record::Params {
+                content: alice.pubkey().to_bytes().to_vec(),
+                record: record.to_owned(),
+            },
+        ),
+        validate_solana_signature(
+            validate_solana_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+                verifier: &alice.pubkey(),
+            },
+            validate_solana_signature::Params { staleness: true },
+        ),
+    ];
+    sign_send_instructions(&mut prg_test_ctx, ixs, vec![&alice])
+        .await
+        .unwrap();
+
+    let check_ix = |record: &str| {
+        sns_records::instruction::check_address_record(
+            check_address_record::Accounts {
+                record: &get_record_key_and_seeds(&domain, record).0,
+                domain: &domain,
+            },
+            check_address_record::Params {
+                record: record.to_owned(),
+            },
+        )
+    };
+    let prove_roa_ixs = |verifier: &Keypair| {
+        vec![
+            sns_records::instruction::write_roa(
+                write_roa::Accounts {
+                    system_program: &system_program::ID,
+                    spl_name_service_program: &spl_name_service::ID,
+                    fee_payer: &alice.pubkey(),
+                    record: &record_key,
+                    domain: &domain,
+                    domain_owner: &alice.pubkey(),
+                    central_state: &sns_records::central_state::KEY,
+                },
+                write_roa::Params {
+                    roa_id: verifier.pubkey().to_bytes().to_vec(),
+                },
+            ),
+            validate_solana_signature(
+                validate_solana_signature::Accounts {
+                    system_program: &system_program::ID,
+                    spl_name_service_program: &spl_name_service::ID,
+                    fee_payer: &alice.pubkey(),
+                    record: &record_key,
+                    domain: &domain,
+                    domain_owner: &alice.pubkey(),
+                    central_state: &sns_records::central_state::KEY,
+                    verifier: &verifier.pubkey(),
+                },
+                validate_solana_signature::Params { staleness: false },
+            ),
+            check_ix(record),
+        ]
+    };
+
+    ////
+    // Without a RoA, the address is not trustworthy
+    ////
+    let res = sign_send_instructions(&mut prg_test_ctx, vec![check_ix(record)], vec![]).await;
+    assert!(res.is_err());
+
+    ////
+    // Bob proves the RoA of a key which differs from the posted address
+    ////
+    let res =
+        sign_send_instructions(&mut prg_test_ctx, prove_roa_ixs(&bob), vec![&alice, &bob]).await;
+    assert!(res.is_err());
+
+    ////
+    // Alice proves the RoA of the posted address
+    ////
+    sign_send_instructions(&mut prg_test_ctx, prove_roa_ixs(&alice), vec![&alice])
+        .await
+        .unwrap();
+    sign_send_instructions(&mut prg_test_ctx, vec![check_ix(record)], vec![])
+        .await
+        .unwrap();
+
+    ////
+    // The check only applies to address records
+    ////
+    let res = sign_send_instructions(&mut prg_test_ctx, vec![check_ix("url")], vec![]).await;
+    assert!(res.is_err());
+
+    ////
+    // Once the RoA is removed, the check fails
+    ////
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![sns_records::instruction::unverify_roa(
+            unverify_roa::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                central_state: &sns_records::central_state::KEY,
+                verifier: &alice.pubkey(),
+            },
+            unverify_roa::Params {},
+        )],
+        vec![&alice],
+    )
+    .await
+    .unwrap();
+    let res = sign_send_instructions(&mut prg_test_ctx, vec![check_ix(record)], vec![]).await;
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn test_validate_address_roa() {
+    use common::utils::{add_domain, program_test_with_domain, sign_send_instructions};
+    use sns_records::instruction::{
+        edit_record, validate_ethereum_signature::eip712_message_hash, write_roa,
+    };
+    use solana_program::keccak::hashv;
+
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let domain = Keypair::new().pubkey();
+    let bob_domain = Keypair::new().pubkey();
+
+    let mut program_test = program_test_with_domain(&alice.pubkey(), &domain);
+    add_domain(&mut program_test, &bob.pubkey(), &bob_domain);
+    let mut prg_test_ctx = program_test.start_with_context().await;
+
+    let eth_secret_key = libsecp256k1::SecretKey::parse(&[7; 32]).unwrap();
+    let eth_public_key = libsecp256k1::PublicKey::from_secret_key(&eth_secret_key);
+    let eth_address = hashv(&[&eth_public_key.serialize()[1..]]).as_ref()[12..].to_vec();
+
+    let record = "ETH";
+    let (record_key, _) = get_record_key_and_seeds(&domain, record);
+
+    let validate_staleness_ix = |record_key| {
+        validate_solana_signature(
+            validate_solana_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+                verifier: &alice.pubkey(),
+            },
+            validate_solana_signature::Params { staleness: true },
+        )
+    };
+    let validate_eth_ix = |content: &[u8]| {
+        let hash = eip712_message_hash(content, &record_key, &alice.pubkey());
+        let (signature, recovery_id) =
+            libsecp256k1::sign(&libsecp256k1::Message::parse(&hash), &eth_secret_key);
+        sns_records::instruction::validate_ethereum_signature_v2(
+            sns_records::instruction::validate_ethereum_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::validate_ethereum_signature::ParamsV2 {
+                validation: Validation::Ethereum,
+                signature: [
+                    signature.serialize().as_ref(),
+                    &[recovery_id.serialize() + 27],
+                ]
+                .concat(),
+                expected_pubkey: eth_address.clone(),
+                format: EthereumMessageFormat::Eip712,
+            },
+        )
+    };
+
+    ////
+    // Post an ETH address which differs from the signing one
+    ////
+    let content = [2; 20];
+    let ixs = vec![
+        sns_records::instruction::allocate_and_post_record(
+            sns_records::instruction::allocate_and_post_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::allocate_and_post_record::Params {
+                content: content.to_vec(),
+                record: record.to_owned(),
+            },
+        ),
+        validate_staleness_ix(&record_key),
+    ];
+    sign_send_instructions(&mut prg_test_ctx, ixs, vec![&alice])
+        .await
+        .unwrap();
+
+    ////
+    // The signature is valid but the RoA does not match the posted address
+    ////
+    let res = sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![validate_eth_ix(&content)],
+        vec![&alice],
+    )
+    .await;
+    assert!(res.is_err());
+
+    ////
+    // Once the signing address is posted, its RoA can be verified
+    ////
+    let ixs = vec![
+        sns_records::instruction::edit_record(
+            edit_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            edit_record::Params {
+                record: record.to_owned(),
+                content: eth_address.clone(),
+            },
+        ),
+        validate_staleness_ix(&record_key),
+        validate_eth_ix(&eth_address),
+    ];
+    sign_send_instructions(&mut prg_test_ctx, ixs, vec![&alice])
+        .await
+        .unwrap();
+
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(record_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
+    assert_eq!(
+        record_hd.right_of_association_validation,
+        Validation::Ethereum as u16
+    );
+
+    ////
+    // Bob cannot verify the RoA of the SOL address of Alice
+    ////
+    let record = "SOL";
+    let (sol_record_key, _) = get_record_key_and_seeds(&domain, record);
+    let ixs = vec![
+        sns_records::instruction::allocate_and_post_record(
+            sns_records::instruction::allocate_and_post_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &sol_record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            sns_records::instruction::allocate_and_post_record::Params {
+                content: alice.pubkey().to_bytes().to_vec(),
+                record: record.to_owned(),
+            },
+        ),
+        validate_staleness_ix(&sol_record_key),
+        sns_records::instruction::write_roa(
+            write_roa::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &sol_record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            write_roa::Params {
+                roa_id: bob.pubkey().to_bytes().to_vec(),
+            },
+        ),
+    ];
+    sign_send_instructions(&mut prg_test_ctx, ixs, vec![&alice])
+        .await
+        .unwrap();
+
+    // The RoA check cannot be skipped by passing another domain
+    for domain in [domain, bob_domain] {
+        let res = sign_send_instructions(
+            &mut prg_test_ctx,
+            vec![validate_solana_signature(
+                validate_solana_signature::Accounts {
+                    system_program: &system_program::ID,
+                    spl_name_service_program: &spl_name_service::ID,
+                    fee_payer: &bob.pubkey(),
+                    record: &sol_record_key,
+                    domain: &domain,
+                    domain_owner: &bob.pubkey(),
+                    central_state: &sns_records::central_state::KEY,
+                    verifier: &bob.pubkey(),
+                },
+                validate_solana_signature::Params { staleness: false },
+            )],
+            vec![&bob],
+        )
+        .await;
+        assert!(res.is_err());
+    }
+
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(sol_record_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let record_hd = RecordHeader::try_from_buffer(&account.data).unwrap();
+    assert_eq!(
+        record_hd.right_of_association_validation,
+        Validation::UnverifiedSolana as u16
+    );
+}
+
+#[tokio::test]
+async fn test_record_content_validation() {
+    use common::utils::{program_test_with_domain, sign_send_instructions};
+    use sns_records::{
+        instruction::{allocate_and_post_record, edit_record, edit_record_preserve_staleness},
+        record::RecordName,
+    };
+
+    let alice = Keypair::new();
+    let domain = Keypair::new().pubkey();
+
+    let program_test = program_test_with_domain(&alice.pubkey(), &domain);
+    let mut prg_test_ctx = program_test.start_with_context().await;
+
+    let record_key = RecordName::Sol.key(&domain);
+    let post_ix = |params| {
+        sns_records::instruction::allocate_and_post_record_v2(
+            allocate_and_post_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            params,
+        )
+    };
+    let edit_ix = |params| {
+        sns_records::instruction::edit_record_v2(
+            edit_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            params,
+        )
+    };
+
+    ////
+    // A SOL record must hold a public key
+    ////
+    let res = sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![post_ix(allocate_and_post_record::ParamsV2::new(
+            RecordName::Sol,
+            b"some random content".to_vec(),
+        ))],
+        vec![&alice],
+    )
+    .await;
+    assert!(res.is_err());
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![post_ix(allocate_and_post_record::ParamsV2::new(
+            RecordName::Sol,
+            alice.pubkey().to_bytes().to_vec(),
+        ))],
+        vec![&alice],
+    )
+    .await
+    .unwrap();
+
+    let res = sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![edit_ix(edit_record::ParamsV2::new(
+            RecordName::Sol,
+            b"some random content".to_vec(),
+        ))],
+        vec![&alice],
+    )
+    .await;
+    assert!(res.is_err());
+
+    ////
+    // The record cannot be edited under the name of another record to skip its validation
+    ////
+    let res = sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![edit_ix(edit_record::ParamsV2::new(
+            RecordName::Txt,
+            b"some random content".to_vec(),
+        ))],
+        vec![&alice],
+    )
+    .await;
+    assert!(res.is_err());
+
+    let ix = sns_records::instruction::edit_record_preserve_staleness(
+        edit_record_preserve_staleness::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &alice.pubkey(),
+            record: &record_key,
+            domain: &domain,
+            domain_owner: &alice.pubkey(),
+            central_state: &sns_records::central_state::KEY,
+        },
+        edit_record_preserve_staleness::Params::new(
+            RecordName::Txt,
+            b"some random content".to_vec(),
+        ),
+    );
+    let res = sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice]).await;
+    assert!(res.is_err());
+
+    ////
+    // The validation is optional
+    ////
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![edit_ix(edit_record::ParamsV2 {
+            validate_content: false,
+            ..edit_record::ParamsV2::new(RecordName::Sol, b"some random content".to_vec())
+        })],
+        vec![&alice],
+    )
+    .await
+    .unwrap();
+
+    // The original instructions never validate the content
+    let (btc_key, _) = get_record_key_and_seeds(&domain, RecordName::Btc.as_str());
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![sns_records::instruction::allocate_and_post_record(
+            allocate_and_post_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &btc_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            allocate_and_post_record::Params {
+                record: RecordName::Btc.into(),
+                content: b"some random content".to_vec(),
+            },
+        )],
+        vec![&alice],
+    )
+    .await
+    .unwrap();
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![sns_records::instruction::edit_record(
+            edit_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            edit_record::Params {
+                record: RecordName::Sol.into(),
+                content: b"some random content".to_vec(),
+            },
+        )],
+        vec![&alice],
+    )
+    .await
+    .unwrap();
+
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(record_key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        &account.data[NameRecordHeader::LEN + RecordHeader::LEN..],
+        b"some random content"
+    );
+}
+
+#[tokio::test]
+async fn test_delete_malformed_record() {
+    use common::utils::{program_test_with_domain, sign_send_instructions};
+    use sns_records::instruction::{
+        batch_records::{self, Operation},
+        delete_record,
+    };
+
+    let alice = Keypair::new();
+    let domain = Keypair::new().pubkey();
+
+    let mut program_test = program_test_with_domain(&alice.pubkey(), &domain);
+
+    // The record headers are truncated
+    let records = ["TXT", "url"];
+    for record in records {
+        let (record_key, _) = get_record_key_and_seeds(&domain, record);
+        let name_record_header = NameRecordHeader {
+            parent_name: domain,
+            owner: sns_records::central_state::KEY,
+            class: sns_records::central_state::KEY,
+        };
+        program_test.add_account(
+            record_key,
+            Account {
+                lamports: 1_000_000_000,
+                data: [name_record_header.try_to_vec().unwrap(), vec![1, 2, 3]].concat(),
+                owner: spl_name_service::ID,
+                ..Account::default()
+            },
+        );
+    }
+    let mut prg_test_ctx = program_test.start_with_context().await;
+    let (txt_key, _) = get_record_key_and_seeds(&domain, records[0]);
+    let (url_key, _) = get_record_key_and_seeds(&domain, records[1]);
+
+    ////
+    // Delete a malformed record
+    ////
+    let ix = sns_records::instruction::delete_record(
+        delete_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &alice.pubkey(),
+            record: &txt_key,
+            domain: &domain,
+            domain_owner: &alice.pubkey(),
+            central_state: &sns_records::central_state::KEY,
+        },
+        delete_record::Params {},
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice])
+        .await
+        .unwrap();
+
+    ////
+    // Delete a malformed record in a batch
+    ////
+    let ix = sns_records::instruction::batch_records(
+        batch_records::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &alice.pubkey(),
+            domain: &domain,
+            domain_owner: &alice.pubkey(),
+            central_state: &sns_records::central_state::KEY,
+            records: &[url_key],
+        },
+        batch_records::Params {
+            operations: vec![Operation::Delete {
+                record: records[1].to_owned(),
+            }],
+        },
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice])
+        .await
+        .unwrap();
+
+    for record_key in [txt_key, url_key] {
+        let account = prg_test_ctx
+            .banks_client
+            .get_account(record_key)
+            .await
+            .unwrap();
+        assert!(account.is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_repair_malformed_record() {
+    use common::utils::{program_test_with_domain, sign_send_instructions};
+    use sns_records::{
+        instruction::{begin_upload, edit_record, finalize_upload},
+        state::record_view::RecordView,
+    };
+
+    let alice = Keypair::new();
+    let domain = Keypair::new().pubkey();
+
+    let mut program_test = program_test_with_domain(&alice.pubkey(), &domain);
+
+    // The content of the TXT record is shorter than its header declares, the header of the url
+    // record is truncated
+    let (txt_key, _) = get_record_key_and_seeds(&domain, "TXT");
+    let (url_key, _) = get_record_key_and_seeds(&domain, "url");
+    for (record_key, data) in [
+        (
+            txt_key,
+            [bytemuck::bytes_of(&RecordHeader::new(100)), b"abc"].concat(),
+        ),
+        (url_key, vec![1, 2, 3]),
+    ] {
+        let name_record_header = NameRecordHeader {
+            parent_name: domain,
+            owner: sns_records::central_state::KEY,
+            class: sns_records::central_state::KEY,
+        };
+        program_test.add_account(
+            record_key,
+            Account {
+                lamports: 1_000_000_000,
+                data: [name_record_header.try_to_vec().unwrap(), data].concat(),
+                owner: spl_name_service::ID,
+                ..Account::default()
+            },
+        );
+    }
+    let mut prg_test_ctx = program_test.start_with_context().await;
+
+    ////
+    // Edit the record whose content is malformed
+    ////
+    let ix = sns_records::instruction::edit_record(
+        edit_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &alice.pubkey(),
+            record: &txt_key,
+            domain: &domain,
+            domain_owner: &alice.pubkey(),
+            central_state: &sns_records::central_state::KEY,
+        },
+        edit_record::Params {
+            record: "TXT".to_owned(),
+            content: b"hello world".to_vec(),
+        },
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice])
+        .await
+        .unwrap();
+
+    ////
+    // Upload the record whose header is malformed
+    ////
+    let content = b"https://sns.id".to_vec();
+    let ix = sns_records::instruction::begin_upload(
+        begin_upload::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &alice.pubkey(),
+            record: &url_key,
+            domain: &domain,
+            domain_owner: &alice.pubkey(),
+            central_state: &sns_records::central_state::KEY,
+        },
+        begin_upload::Params {
+            record: "url".to_owned(),
+            content_length: content.len() as u32,
+        },
+    );
+    let write_ix = sns_records::instruction::write_chunk(
+        sns_records::instruction::write_chunk::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &alice.pubkey(),
+            record: &url_key,
+            domain: &domain,
+            domain_owner: &alice.pubkey(),
+            central_state: &sns_records::central_state::KEY,
+        },
+        sns_records::instruction::write_chunk::Params {
+            offset: 0,
+            data: content.clone(),
+        },
+    );
+    let finalize_ix = sns_records::instruction::finalize_upload(
+        finalize_upload::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &alice.pubkey(),
+            record: &url_key,
+            domain: &domain,
+            domain_owner: &alice.pubkey(),
+            central_state: &sns_records::central_state::KEY,
+        },
+        finalize_upload::Params {
+            content_length: content.len() as u32,
+            content_hash: solana_program::hash::hash(&content).to_bytes(),
+        },
+    );
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![ix, write_ix, finalize_ix],
+        vec![&alice],
+    )
+    .await
+    .unwrap();
+
+    ////
+    // State verification
+    ////
+    for (record_key, content) in [(txt_key, &b"hello world"[..]), (url_key, &content)] {
+        let account = prg_test_ctx
+            .banks_client
+            .get_account(record_key)
+            .await
+            .unwrap()
+            .unwrap();
+        let record = RecordView::new(&account.data).unwrap();
+        assert_eq!(record.content(), content);
+        assert_eq!(record.staleness_validation(), Validation::None);
+    }
+}
+
+#[tokio::test]
+async fn test_pending_upload_record() {
+    use common::utils::{program_test_with_domain, sign_send_instructions};
+    use sns_records::{
+        instruction::{
+            begin_upload, edit_record, edit_record_preserve_staleness, patch_record, write_roa,
+        },
+        state::record_header::VersionedRecordHeader,
+    };
+
+    let alice = Keypair::new();
+    let domain = Keypair::new().pubkey();
+
+    let program_test = program_test_with_domain(&alice.pubkey(), &domain);
+    let mut prg_test_ctx = program_test.start_with_context().await;
+
+    let record = "TXT";
+    let (record_key, _) = get_record_key_and_seeds(&domain, record);
+    let ix = sns_records::instruction::begin_upload(
+        begin_upload::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &alice.pubkey(),
+            record: &record_key,
+            domain: &domain,
+            domain_owner: &alice.pubkey(),
+            central_state: &sns_records::central_state::KEY,
+        },
+        begin_upload::Params {
+            record: record.to_owned(),
+            content_length: 11,
+        },
+    );
+    let write_ix = sns_records::instruction::write_chunk(
+        sns_records::instruction::write_chunk::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &alice.pubkey(),
+            record: &record_key,
+            domain: &domain,
+            domain_owner: &alice.pubkey(),
+            central_state: &sns_records::central_state::KEY,
+        },
+        sns_records::instruction::write_chunk::Params {
+            offset: 0,
+            data: b"hello".to_vec(),
+        },
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix, write_ix], vec![&alice])
+        .await
+        .unwrap();
+
+    let validate_staleness_ix = || {
+        sns_records::instruction::validate_solana_signature(
+            validate_solana_signature::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+                verifier: &alice.pubkey(),
+            },
+            validate_solana_signature::Params { staleness: true },
+        )
+    };
+
+    ////
+    // The partially written content can neither be validated nor modified
+    ////
+    let ixs = vec![
+        validate_staleness_ix(),
+        sns_records::instruction::write_roa(
+            write_roa::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            write_roa::Params {
+                roa_id: alice.pubkey().to_bytes().to_vec(),
+            },
+        ),
+        sns_records::instruction::patch_record(
+            patch_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            patch_record::Params {
+                patches: vec![patch_record::Patch {
+                    offset: 0,
+                    data: b"H".to_vec(),
+                }],
+            },
+        ),
+        sns_records::instruction::edit_record_preserve_staleness(
+            edit_record_preserve_staleness::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &alice.pubkey(),
+                record: &record_key,
+                domain: &domain,
+                domain_owner: &alice.pubkey(),
+                central_state: &sns_records::central_state::KEY,
+            },
+            edit_record_preserve_staleness::Params {
+                record: record.to_owned(),
+                content: b"hello world".to_vec(),
+            },
+        ),
+    ];
+    for ix in ixs {
+        let res = sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice]).await;
+        assert!(res.is_err());
+    }
+
+    ////
+    // Editing the record cancels the upload
+    ////
+    let ix = sns_records::instruction::edit_record(
+        edit_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &alice.pubkey(),
+            record: &record_key,
+            domain: &domain,
+            domain_owner: &alice.pubkey(),
+            central_state: &sns_records::central_state::KEY,
+        },
+        edit_record::Params {
+            record: record.to_owned(),
+            content: b"hello world".to_vec(),
+        },
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice])
+        .await
+        .unwrap();
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(record_key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!VersionedRecordHeader::try_from_buffer(&account.data)
+        .unwrap()
+        .is_pending_upload());
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![validate_staleness_ix()],
+        vec![&alice],
+    )
+    .await
+    .unwrap();
 }
@@ -0,0 +1,96 @@
+use sns_records::{
+    error::SnsRecordsError,
+    state::{record::Record, record_header::RecordHeader, validation::Validation},
+    verifier::SignedRecord,
+};
+use solana_program::{program_pack::Pack, pubkey::Pubkey};
+use spl_name_service::state::NameRecordHeader;
+
+fn serialize_record(
+    staleness_validation: Validation,
+    roa_validation: Validation,
+    ids: &[u8],
+    content: &[u8],
+) -> Vec<u8> {
+    let header = RecordHeader {
+        staleness_validation: staleness_validation as u16,
+        right_of_association_validation: roa_validation as u16,
+        content_length: content.len() as u32,
+    };
+    [
+        &[0; NameRecordHeader::LEN][..],
+        bytemuck::bytes_of(&header),
+        ids,
+        content,
+    ]
+    .concat()
+}
+
+#[test]
+fn test_record_deserialize() {
+    // Wormhole chain ID of Ethereum
+    let chain_id: u16 = 2;
+    let owner_address = [7; 32];
+    let eth_address = [9; 20];
+    let content = "0x0909090909090909090909090909090909090909".as_bytes();
+
+    let data = serialize_record(
+        Validation::XChain,
+        Validation::Ethereum,
+        &[&chain_id.to_le_bytes()[..], &owner_address, &eth_address].concat(),
+        content,
+    );
+    let record = Record::deserialize(&data).unwrap();
+
+    assert_eq!(record.staleness_validation(), Validation::XChain);
+    assert_eq!(
+        record.right_of_association_validation(),
+        Validation::Ethereum
+    );
+    assert_eq!(record.header().content_length, content.len() as u32);
+    assert_eq!(
+        record.staleness_id(),
+        &[&chain_id.to_le_bytes()[..], &owner_address].concat()[..]
+    );
+    assert_eq!(record.roa_id(), &eth_address);
+    assert_eq!(record.content(), content);
+    assert_eq!(
+        record.data(),
+        &data[NameRecordHeader::LEN + RecordHeader::LEN..]
+    );
+    assert_eq!(
+        record.xchain_staleness_id().unwrap(),
+        (chain_id, owner_address)
+    );
+    // The signed part of the record decodes the staleness ID the same way
+    let record_key = Pubkey::new_unique();
+    assert_eq!(
+        SignedRecord::from_record_data(&record_key, &data)
+            .unwrap()
+            .xchain_staleness_id()
+            .unwrap(),
+        (chain_id, owner_address)
+    );
+
+    ////
+    // Only XChain staleness IDs can be decoded
+    ////
+    let data = serialize_record(Validation::Solana, Validation::None, &[1; 32], content);
+    let record = Record::deserialize(&data).unwrap();
+    assert_eq!(record.staleness_id(), &[1; 32]);
+    assert!(record.roa_id().is_empty());
+    assert_eq!(record.content(), content);
+    assert!(matches!(
+        record.xchain_staleness_id(),
+        Err(SnsRecordsError::UnsupportedValidation)
+    ));
+
+    ////
+    // Truncated validation IDs are rejected
+    ////
+    let data = serialize_record(Validation::XChain, Validation::None, &[0; 33], &[]);
+    assert!(matches!(
+        Record::deserialize(&data),
+        Err(SnsRecordsError::OutOfBound)
+    ));
+}
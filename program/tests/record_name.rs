@@ -0,0 +1,150 @@
+use std::str::FromStr;
+
+use sns_records::{
+    error::SnsRecordsError,
+    instruction::{allocate_and_post_record, allocate_record, edit_record},
+    record::{check_record_content, RecordName, MAX_TEXT_LENGTH},
+    utils::{get_hashed_name, get_record_key_and_seeds},
+};
+use solana_program::pubkey::Pubkey;
+
+#[test]
+fn test_record_names() {
+    for record in RecordName::ALL {
+        assert_eq!(RecordName::from_str(record.as_str()).unwrap(), *record);
+        assert_eq!(record.to_string(), record.as_str());
+        assert_eq!(RecordName::find_misspelled(record.as_str()), None);
+    }
+    assert_eq!(RecordName::from_str("IPFS").unwrap(), RecordName::Ipfs);
+    assert_eq!(RecordName::from_str("INJ").unwrap(), RecordName::Injective);
+    assert_eq!(
+        RecordName::from_str("twitter").unwrap(),
+        RecordName::Twitter
+    );
+
+    // Names are case sensitive
+    for misspelled in ["SoL", "sol", "Twitter", "aaaa"] {
+        assert!(matches!(
+            RecordName::from_str(misspelled),
+            Err(SnsRecordsError::UnknownRecord)
+        ));
+    }
+    assert_eq!(RecordName::find_misspelled("SoL"), Some(RecordName::Sol));
+    assert_eq!(
+        RecordName::find_misspelled("Twitter"),
+        Some(RecordName::Twitter)
+    );
+    assert_eq!(RecordName::find_misspelled("my-custom-record"), None);
+}
+
+#[test]
+fn test_record_key() {
+    let domain = Pubkey::new_unique();
+    assert_eq!(
+        RecordName::Sol.key(&domain),
+        get_record_key_and_seeds(&domain, "SOL").0
+    );
+    assert_eq!(RecordName::Sol.hashed_name(), get_hashed_name("SOL"));
+    assert_ne!(RecordName::Sol.key(&domain), RecordName::Eth.key(&domain));
+
+    let params =
+        allocate_and_post_record::ParamsV2::new(RecordName::Url, b"https://sns.id".to_vec());
+    assert_eq!(params.record, "url");
+    assert_eq!(params.content, b"https://sns.id");
+    assert!(params.validate_content);
+    let params = edit_record::Params::new(RecordName::Url, b"https://sns.id".to_vec());
+    assert_eq!(params.record, "url");
+    assert_eq!(
+        allocate_record::Params::new(RecordName::Txt, 10).record,
+        "TXT"
+    );
+}
+
+#[test]
+fn test_check_content() {
+    let valid: &[(RecordName, &[u8])] = &[
+        (RecordName::Sol, &[1; 32]),
+        (RecordName::Eth, &[2; 20]),
+        (RecordName::Bsc, &[2; 20]),
+        (
+            RecordName::Btc,
+            b"bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+        ),
+        (
+            RecordName::Injective,
+            b"inj14au322k9munkmx5wrchz9q30juf5wjgz2cfqku",
+        ),
+        (
+            RecordName::Ipfs,
+            b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+        ),
+        (
+            RecordName::Ipfs,
+            b"ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        (RecordName::A, &[127, 0, 0, 1]),
+        (RecordName::Aaaa, &[0; 16]),
+        (RecordName::Url, b"https://sns.id"),
+        (RecordName::Url, b"HTTP://bonfida.org/path?query"),
+        (RecordName::Txt, "héllo wörld".as_bytes()),
+        (RecordName::Email, b"hello@bonfida.org"),
+    ];
+    for (record, content) in valid {
+        record.check_content(content).unwrap();
+    }
+
+    let too_long = vec![b'a'; MAX_TEXT_LENGTH + 1];
+    let invalid: &[(RecordName, &[u8])] = &[
+        (RecordName::Sol, b"some random content"),
+        (
+            RecordName::Eth,
+            b"0x4bfbfd1e018f9f27eeb788160579daf7e2cd7da7",
+        ),
+        (
+            RecordName::Btc,
+            b"bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5",
+        ),
+        // INJ is an address record, not a text record
+        (RecordName::Injective, b"hello world"),
+        (
+            RecordName::Injective,
+            b"inj14au322k9munkmx5wrchz9q30juf5wjgz2cfqkv",
+        ),
+        (
+            RecordName::Injective,
+            b"bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+        ),
+        (
+            RecordName::Ipfs,
+            b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbd0",
+        ),
+        (
+            RecordName::Ipfs,
+            b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbz",
+        ),
+        (RecordName::Ipfs, b"hello"),
+        (RecordName::A, &[127, 0, 0]),
+        (RecordName::Aaaa, &[0; 4]),
+        (RecordName::Url, b"javascript:alert(1)"),
+        (RecordName::Url, b"ftp://bonfida.org"),
+        (RecordName::Url, b"https://"),
+        (RecordName::Url, b"https://bonfida .org"),
+        (RecordName::Txt, &[0xff, 0xfe]),
+        (RecordName::Txt, &too_long),
+    ];
+    for (record, content) in invalid {
+        assert!(
+            matches!(
+                record.check_content(content),
+                Err(SnsRecordsError::InvalidRecordContent)
+            ),
+            "{} {:?}",
+            record,
+            content
+        );
+    }
+
+    // Custom records are not checked
+    check_record_content("SOL", b"some random content").unwrap_err();
+    check_record_content("my-custom-record", &[0xff, 0xfe]).unwrap();
+}
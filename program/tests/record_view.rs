@@ -0,0 +1,162 @@
+use rand::Rng;
+use sns_records::{
+    entrypoint::process_instruction,
+    error::SnsRecordsError,
+    state::{
+        record_header::RecordHeader,
+        record_view::RecordView,
+        validation::{get_validation_length, Validation},
+    },
+};
+use solana_program::{program_error::ProgramError, program_pack::Pack};
+use spl_name_service::state::NameRecordHeader;
+
+const ITERATIONS: usize = 10_000;
+
+const VALIDATIONS: [Validation; 12] = [
+    Validation::None,
+    Validation::Solana,
+    Validation::Ethereum,
+    Validation::UnverifiedSolana,
+    Validation::XChain,
+    Validation::Bitcoin,
+    Validation::Nostr,
+    Validation::Passkey,
+    Validation::UnverifiedEthereum,
+    Validation::UnverifiedBitcoin,
+    Validation::UnverifiedNostr,
+    Validation::UnverifiedPasskey,
+];
+
+fn random_bytes(rng: &mut impl Rng, len: usize) -> Vec<u8> {
+    let mut bytes = vec![0; len];
+    rng.fill_bytes(&mut bytes);
+    bytes
+}
+
+/// Serializes a record account with random IDs of the expected lengths
+fn random_record(
+    rng: &mut impl Rng,
+    staleness_validation: Validation,
+    roa_validation: Validation,
+    content: &[u8],
+) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+    let staleness_id = random_bytes(rng, get_validation_length(staleness_validation) as usize);
+    let roa_id = random_bytes(rng, get_validation_length(roa_validation) as usize);
+    let header = RecordHeader {
+        staleness_validation: staleness_validation as u16,
+        right_of_association_validation: roa_validation as u16,
+        content_length: content.len() as u32,
+    };
+    let data = [
+        &random_bytes(rng, NameRecordHeader::LEN)[..],
+        bytemuck::bytes_of(&header),
+        &staleness_id,
+        &roa_id,
+        content,
+    ]
+    .concat();
+    (data, staleness_id, roa_id)
+}
+
+#[test]
+fn test_record_view_roundtrip() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..ITERATIONS {
+        let staleness_validation = VALIDATIONS[rng.gen_range(0..VALIDATIONS.len())];
+        let roa_validation = VALIDATIONS[rng.gen_range(0..VALIDATIONS.len())];
+        let content_len = rng.gen_range(0..256);
+        let content = random_bytes(&mut rng, content_len);
+        let (data, staleness_id, roa_id) =
+            random_record(&mut rng, staleness_validation, roa_validation, &content);
+
+        let view = RecordView::new(&data).unwrap();
+        assert_eq!(
+            view.staleness_validation() as u16,
+            staleness_validation as u16
+        );
+        assert_eq!(
+            view.right_of_association_validation() as u16,
+            roa_validation as u16
+        );
+        assert_eq!(view.header().content_length, content.len() as u32);
+        assert_eq!(view.staleness_id(), &staleness_id[..]);
+        assert_eq!(view.roa_id(), &roa_id[..]);
+        assert_eq!(view.content(), &content[..]);
+        assert_eq!(
+            view.after_staleness_id(),
+            &[&roa_id, &content[..]].concat()[..]
+        );
+        assert_eq!(
+            view.data_len(),
+            data.len() - NameRecordHeader::LEN - RecordHeader::LEN
+        );
+
+        // Truncating the validation IDs or the headers is an error
+        let ids_end = NameRecordHeader::LEN + RecordHeader::LEN + staleness_id.len() + roa_id.len();
+        let truncated_len = rng.gen_range(0..ids_end);
+        assert!(RecordView::new(&data[..truncated_len]).is_err());
+        // Truncating the content is not
+        let truncated_len = rng.gen_range(ids_end..=data.len());
+        assert_eq!(
+            RecordView::new(&data[..truncated_len]).unwrap().content(),
+            &content[..truncated_len - ids_end]
+        );
+    }
+}
+
+#[test]
+fn test_record_view_invalid_validation() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..ITERATIONS {
+        let (mut data, _, _) = random_record(&mut rng, Validation::None, Validation::None, &[]);
+        let mut header = RecordHeader::try_from_buffer(&data).unwrap();
+        // `u16::MAX` marks a V2 header
+        let invalid = rng.gen_range(VALIDATIONS.len() as u16..u16::MAX);
+        if rng.gen() {
+            header.staleness_validation = invalid;
+        } else {
+            header.right_of_association_validation = invalid;
+        }
+        data[NameRecordHeader::LEN..NameRecordHeader::LEN + RecordHeader::LEN]
+            .copy_from_slice(bytemuck::bytes_of(&header));
+
+        assert!(matches!(
+            RecordView::new(&data),
+            Err(SnsRecordsError::DataTypeMismatch)
+        ));
+    }
+}
+
+#[test]
+fn test_record_view_random_data() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..ITERATIONS {
+        let len = rng.gen_range(0..NameRecordHeader::LEN + RecordHeader::LEN + 128);
+        let mut data = random_bytes(&mut rng, len);
+        // Bias the validations towards valid values to go past the header checks
+        if len >= NameRecordHeader::LEN + RecordHeader::LEN && rng.gen() {
+            data[NameRecordHeader::LEN] = rng.gen_range(0..VALIDATIONS.len() as u8);
+            data[NameRecordHeader::LEN + 1] = 0;
+            data[NameRecordHeader::LEN + 2] = rng.gen_range(0..VALIDATIONS.len() as u8);
+            data[NameRecordHeader::LEN + 3] = 0;
+        }
+
+        // Must never panic
+        if let Ok(view) = RecordView::new(&data) {
+            assert_eq!(
+                [view.staleness_id(), view.roa_id(), view.content()].concat(),
+                &data[NameRecordHeader::LEN + RecordHeader::LEN..]
+            );
+        }
+    }
+}
+
+#[test]
+fn test_empty_instruction_data() {
+    let res = process_instruction(&sns_records::ID, &[], &[]);
+    assert_eq!(res, Err(ProgramError::InvalidInstructionData));
+}
@@ -0,0 +1,115 @@
+use std::convert::TryInto;
+
+use sns_records::schnorr::verify;
+
+/// (public key, message, r, s, valid), the valid signatures were generated with the BIP-340
+/// reference implementation
+const VECTORS: &[(&str, &str, &str, &str, bool)] = &[
+    // BIP-340 test vector 0
+    (
+        "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "e907831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca8215",
+        "25f66a4a85ea8b71e482a74f382d2ce5ebeee8fdb2172f477df4900d310536c0",
+        true,
+    ),
+    // Even y, 32 byte message
+    (
+        "a2a95de68df3cd3023364ea9a44c2c3f9ac7587adee0662241fb36f8e71a0b7d",
+        "10fb681e5e6f0791f1b4a4462becad2d1b8b3d50a3d11c38a64e34b366195285",
+        "78185cc3236f3e618b1b0e5a4820e13a1162421f6c103cb98ead20e8c0c42d09",
+        "4fae4fb164b4fc176bef874d90bd83dd0e29d67742606fee1213ab41afccce35",
+        true,
+    ),
+    // Odd y, 32 byte message
+    (
+        "2705b99216026cd7551c2b367ccdd86e00c091db3ac72386fa8f2729ca57c67d",
+        "1017270b62871a2a5c49197d5613f55289ef07228bbcd63142c6aa41528d443a",
+        "83953b8d1101de4db987d7d869f117dd7a9907ceb7459f107fc55b890fd0c25e",
+        "f695b1e6b8fd6ad9c85022b191247740713f322cd0a707539423b4406570ada9",
+        true,
+    ),
+    // Even y, 0 byte message
+    (
+        "ededdd00b7e4bea642b5cc6b459aef7c5e89eb82db9141078b86cba476e01e0e",
+        "",
+        "464e45f17673689db0f375bafb8931ab0e59bc81e8dd29ffec2aebe51cde7fdb",
+        "ab58b6e3bcfb7e9691d8460415f8b8f4adf886c0f74f13eb7317c4e6fe86e4b3",
+        true,
+    ),
+    // Even y, 77 byte message
+    (
+        "febcdd56e5c639af38bd937e725803f4f193284ccf120f8294b42cecee27732e",
+        "a21a34790d3f9500557103d4fa928af2d38f60f8aa6afffaeca13ade4dbebf6bb0f9c4579ae8d9e4f345f011df55a3d408cd6326b0c00f599fbe8674ac1b253e16a953f4f7310ae79ca041f8a4",
+        "87df0db22a29fff3978fba95fd3c2a5e1a9466c005293be20a0d5d4fa8b131f3",
+        "8582ea1ff1c3a9bfa79d46a135473809ffac2f43e6eb34e78c46ff60980a1447",
+        true,
+    ),
+    // Tampered r
+    (
+        "a2a95de68df3cd3023364ea9a44c2c3f9ac7587adee0662241fb36f8e71a0b7d",
+        "10fb681e5e6f0791f1b4a4462becad2d1b8b3d50a3d11c38a64e34b366195285",
+        "78185cc3236e3e618b1b0e5a4820e13a1162421f6c103cb98ead20e8c0c42d09",
+        "4fae4fb164b4fc176bef874d90bd83dd0e29d67742606fee1213ab41afccce35",
+        false,
+    ),
+    // Tampered message
+    (
+        "a2a95de68df3cd3023364ea9a44c2c3f9ac7587adee0662241fb36f8e71a0b7d",
+        "11fb681e5e6f0791f1b4a4462becad2d1b8b3d50a3d11c38a64e34b366195285",
+        "78185cc3236f3e618b1b0e5a4820e13a1162421f6c103cb98ead20e8c0c42d09",
+        "4fae4fb164b4fc176bef874d90bd83dd0e29d67742606fee1213ab41afccce35",
+        false,
+    ),
+    // Wrong public key
+    (
+        "2705b99216026cd7551c2b367ccdd86e00c091db3ac72386fa8f2729ca57c67d",
+        "10fb681e5e6f0791f1b4a4462becad2d1b8b3d50a3d11c38a64e34b366195285",
+        "78185cc3236f3e618b1b0e5a4820e13a1162421f6c103cb98ead20e8c0c42d09",
+        "4fae4fb164b4fc176bef874d90bd83dd0e29d67742606fee1213ab41afccce35",
+        false,
+    ),
+    // Public key not on the curve
+    (
+        "a2a95de68df3cd3023364ea9a44c2c3f9ac7587adee0662241fb36f8e71a0b7f",
+        "10fb681e5e6f0791f1b4a4462becad2d1b8b3d50a3d11c38a64e34b366195285",
+        "78185cc3236f3e618b1b0e5a4820e13a1162421f6c103cb98ead20e8c0c42d09",
+        "4fae4fb164b4fc176bef874d90bd83dd0e29d67742606fee1213ab41afccce35",
+        false,
+    ),
+    // s equal to the curve order
+    (
+        "a2a95de68df3cd3023364ea9a44c2c3f9ac7587adee0662241fb36f8e71a0b7d",
+        "10fb681e5e6f0791f1b4a4462becad2d1b8b3d50a3d11c38a64e34b366195285",
+        "78185cc3236f3e618b1b0e5a4820e13a1162421f6c103cb98ead20e8c0c42d09",
+        "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+        false,
+    ),
+    // Negated s
+    (
+        "a2a95de68df3cd3023364ea9a44c2c3f9ac7587adee0662241fb36f8e71a0b7d",
+        "10fb681e5e6f0791f1b4a4462becad2d1b8b3d50a3d11c38a64e34b366195285",
+        "78185cc3236f3e618b1b0e5a4820e13a1162421f6c103cb98ead20e8c0c42d09",
+        "b051b04e9b4b03e8941078b26f427c21ac85066f6ce8304dadbeb34b2069730c",
+        false,
+    ),
+];
+
+#[test]
+fn test_bip340_vectors() {
+    for (i, (pubkey, message, r, s, valid)) in VECTORS.iter().enumerate() {
+        let pubkey: [u8; 32] = hex::decode(pubkey).unwrap().try_into().unwrap();
+        let message = hex::decode(message).unwrap();
+        let signature: [u8; 64] = [hex::decode(r).unwrap(), hex::decode(s).unwrap()]
+            .concat()
+            .try_into()
+            .unwrap();
+
+        assert_eq!(
+            verify(&pubkey, &message, &signature),
+            *valid,
+            "Vector {}",
+            i
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use sns_records::{
+    error::SnsRecordsError,
+    state::validation::{
+        Validation::{self, *},
+        ValidationEvent::{self, *},
+        ValidationState,
+    },
+};
+
+const VALIDATIONS: [Validation; 12] = [
+    None,
+    Solana,
+    Ethereum,
+    UnverifiedSolana,
+    XChain,
+    Bitcoin,
+    Nostr,
+    Passkey,
+    UnverifiedEthereum,
+    UnverifiedBitcoin,
+    UnverifiedNostr,
+    UnverifiedPasskey,
+];
+
+fn state(staleness: Validation, right_of_association: Validation) -> ValidationState {
+    ValidationState {
+        staleness,
+        right_of_association,
+    }
+}
+
+fn assert_illegal(from: ValidationState, event: ValidationEvent) {
+    assert!(
+        matches!(
+            from.transition(event),
+            Err(SnsRecordsError::InvalidValidationTransition)
+        ),
+        "{:?} -> {:?}",
+        from,
+        event
+    );
+}
+
+#[test]
+fn test_legal_transitions() {
+    let stale = state(Solana, None);
+
+    // None -> Unverified -> Verified -> None
+    let declared = stale.transition(DeclareRoa(UnverifiedSolana)).unwrap();
+    assert_eq!(declared, state(Solana, UnverifiedSolana));
+    let verified = declared.transition(ValidateRoa(Solana)).unwrap();
+    assert_eq!(verified, state(Solana, Solana));
+    assert_eq!(verified.transition(ClearRoa).unwrap(), stale);
+
+    // Signed messages can be verified without a declaration
+    assert_eq!(
+        stale.transition(ValidateRoa(Ethereum)).unwrap(),
+        state(Solana, Ethereum)
+    );
+
+    // A declaration can be replaced before it is verified
+    assert_eq!(
+        declared.transition(DeclareRoa(UnverifiedNostr)).unwrap(),
+        state(Solana, UnverifiedNostr)
+    );
+
+    // The staleness clears the RoA
+    assert_eq!(
+        verified.transition(ValidateStaleness(XChain)).unwrap(),
+        state(XChain, None)
+    );
+
+    // A reset clears everything
+    assert_eq!(verified.transition(Reset).unwrap(), ValidationState::RESET);
+}
+
+#[test]
+fn test_illegal_transitions() {
+    for staleness in [None, Solana, XChain] {
+        for roa in VALIDATIONS {
+            let from = state(staleness, roa);
+
+            // The staleness can only be proven by the domain owner
+            for validation in VALIDATIONS {
+                if !matches!(validation, Solana | XChain) {
+                    assert_illegal(from, ValidateStaleness(validation));
+                }
+            }
+
+            for validation in VALIDATIONS {
+                // Only unverified kinds can be declared, and not over a verified RoA
+                if !validation.is_unverified() || !(roa == None || roa.is_unverified()) {
+                    assert_illegal(from, DeclareRoa(validation));
+                }
+
+                // Only verified RoA kinds can be proven
+                if validation.unverified().is_none() {
+                    assert_illegal(from, ValidateRoa(validation));
+                }
+            }
+
+            // A verified RoA cannot be overwritten by another kind
+            for (verified, unverified) in VALIDATIONS
+                .iter()
+                .filter_map(|v| v.unverified().map(|u| (*v, u)))
+            {
+                if roa != None && roa != verified && roa != unverified {
+                    assert_illegal(from, ValidateRoa(verified));
+                }
+            }
+
+            // Solana verifiers must be declared beforehand
+            if roa == None {
+                assert_illegal(from, ValidateRoa(Solana));
+            }
+        }
+    }
+}